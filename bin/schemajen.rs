@@ -1,23 +1,35 @@
 use schemajen::*;
 
 fn main() {
-    let args = std::env::args().collect::<Vec<_>>();
+    let (flags, args) = std::env::args().partition::<Vec<_>, _>(|s| s.starts_with("--"));
     let bin_name = env!("CARGO_BIN_NAME");
 
     let support_str = ACCUMULATOR_SUPPORT_LIST
         .iter()
         .fold(String::new(), |total, s| total + "\t" + s + "\n");
 
-    if args.iter().any(|s| s == "-h" || s == "--help") {
+    if args.iter().any(|s| s == "-h") || flags.iter().any(|s| s == "--help") {
         eprintln!(
             "{bin_name}, v{}
 
 Learn more at https://github.com/davnotdev/schemajen.
 
 Auto-magically convert JSON into language bindings.
-Run with `{bin_name} [accumulator] [typename] [file]`.
+Run with `{bin_name} [flags] [accumulator] [typename] [file...]`.
+Passing multiple files merges them as samples of the same type.
+OpenAPI documents name their own types, so run those with `{bin_name} --input=openapi [accumulator] [file]`.
 View this very message with `{bin_name} --help` or `{bin_name} -h`.
 
+Flags:
+
+\t--input=<format>\tRead files as `json` samples, the default, as `yaml` or `toml` samples,
+\t\t\tas `ndjson` with a sample per line, as a JSON `schema`,
+\t\t\tor as an `openapi` 3 document.
+\t--unions\tAllow values of differing types to become a union type.
+\t--idiomatic-casing\tRename fields to the language's usual casing.
+\t--names=<file>\tForce type names with a JSON object of JSONPaths to names,
+\t\t\tsuch as `{{ \"$.data.items[*]\": \"Product\" }}`.
+
 This version was compiled with the following accumulators:
 
 {}",
@@ -27,34 +39,110 @@ This version was compiled with the following accumulators:
         return;
     }
 
+    let mut options = GenerateOptions::default();
+    let mut idiomatic_casing = false;
+    let mut input = String::from("json");
+    for flag in flags {
+        match flag.as_str() {
+            "--input=json" | "--input=yaml" | "--input=toml" | "--input=ndjson"
+            | "--input=schema" | "--input=openapi" => input = flag["--input=".len()..].to_owned(),
+            "--unions" => options.unions = true,
+            "--idiomatic-casing" => idiomatic_casing = true,
+            _ if flag.starts_with("--names=") => match read_names(&flag["--names=".len()..]) {
+                Ok(names) => options.names = names,
+                Err(e) => {
+                    eprintln!("Failed to read names with: {}", e);
+                    return;
+                }
+            },
+            _ => {
+                eprintln!("Unknown flag `{flag}`, see `{bin_name} -h`");
+                return;
+            }
+        }
+    }
+
     let Some(accumulator) = args.get(1) else {
         eprintln!("Expected language accumulator, see `{bin_name} -h`");
         return;
     };
 
-    let Some(typename) = args.get(2) else {
-        eprintln!("Expected type name, see `{bin_name} -h`");
-        return;
+    let (typename, filenames) = if input == "openapi" {
+        ("", args.get(2..).unwrap_or_default())
+    } else {
+        let Some(typename) = args.get(2) else {
+            eprintln!("Expected type name, see `{bin_name} -h`");
+            return;
+        };
+        (typename.as_str(), args.get(3..).unwrap_or_default())
     };
 
-    let Some(filename) = args.get(3) else {
+    if filenames.is_empty() {
         eprintln!("Expected file, see `{bin_name} -h`");
         return;
+    }
+
+    let Some(mut accumulator) = accumulator_choose_with_str(accumulator) else {
+        eprintln!("That accumulator does not exist");
+        return;
     };
+    if idiomatic_casing {
+        accumulator.use_idiomatic_casing();
+    }
 
-    let file = std::fs::read_to_string(filename);
-    if let Err(e) = file {
+    //  Logs can be large, so they are streamed line by line instead.
+    if input == "ndjson" {
+        let readers = filenames
+            .iter()
+            .map(|filename| std::fs::File::open(filename).map(std::io::BufReader::new))
+            .collect::<Result<Vec<_>, _>>();
+        if let Err(e) = readers {
+            eprintln!("Failed to open file with: {}", e);
+            return;
+        };
+        let res = generate_from_ndjson_with_options(
+            accumulator.as_mut(),
+            typename,
+            readers.unwrap(),
+            &options,
+        );
+        print_result(res);
+        return;
+    }
+
+    let files = filenames
+        .iter()
+        .map(std::fs::read_to_string)
+        .collect::<Result<Vec<_>, _>>();
+    if let Err(e) = files {
         eprintln!("Failed to open file with: {}", e);
         return;
     };
-    let file = file.unwrap();
-
-    let Some(mut accumulator) = accumulator_choose_with_str(accumulator) else {
-            eprintln!("That accumulator does not exist");
+    let files = files.unwrap();
+    let files = files.iter().map(String::as_str).collect::<Vec<_>>();
+    let res = match (input.as_str(), files.as_slice()) {
+        ("schema", [schema]) => {
+            generate_from_schema_with_options(accumulator.as_mut(), typename, schema, &options)
+        }
+        ("openapi", [document]) => {
+            generate_from_openapi_with_options(accumulator.as_mut(), document, &options)
+        }
+        ("schema" | "openapi", _) => {
+            eprintln!("Expected exactly one {input} file, see `{bin_name} -h`");
             return;
+        }
+        ("yaml", _) => {
+            generate_from_yaml_with_options(accumulator.as_mut(), typename, &files, &options)
+        }
+        ("toml", _) => {
+            generate_from_toml_with_options(accumulator.as_mut(), typename, &files, &options)
+        }
+        _ => generate_with_options(accumulator.as_mut(), typename, &files, &options),
     };
-    let res = generate(accumulator.as_mut(), typename, &file);
+    print_result(res);
+}
 
+fn print_result(res: Result<String, Error>) {
     if let Err(e) = &res {
         eprintln!("Codegen failed with: {:?}", e);
         return;
@@ -62,3 +150,19 @@ This version was compiled with the following accumulators:
 
     println!("{}", res.unwrap());
 }
+
+/// Read a JSON object mapping JSONPaths to type names.
+fn read_names(filename: &str) -> Result<std::collections::HashMap<String, String>, String> {
+    let file = std::fs::read_to_string(filename).map_err(|e| e.to_string())?;
+    let names = json::parse(&file).map_err(|e| e.to_string())?;
+    if !names.is_object() {
+        return Err(String::from("expected a JSON object"));
+    }
+    names
+        .entries()
+        .map(|(path, name)| match name.as_str() {
+            Some(name) => Ok((path.to_owned(), name.to_owned())),
+            None => Err(format!("expected a string name for `{}`", path)),
+        })
+        .collect()
+}
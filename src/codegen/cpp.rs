@@ -0,0 +1,432 @@
+use super::*;
+
+const KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char8_t",
+    "char16_t",
+    "char32_t",
+    "class",
+    "co_await",
+    "co_return",
+    "co_yield",
+    "compl",
+    "concept",
+    "const",
+    "const_cast",
+    "consteval",
+    "constexpr",
+    "constinit",
+    "continue",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "requires",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+];
+
+enum FieldKind {
+    Plain,
+    /// Holds the type inside of the `std::optional`.
+    Nullable(String),
+    /// Holds the type inside of the `std::optional`.
+    Optional(String),
+}
+
+struct Field {
+    name: String,
+    key: String,
+    ty: String,
+    kind: FieldKind,
+    /// Held by a `std::shared_ptr`, see [`CppAccumulator::push_field`].
+    pointer: bool,
+}
+
+struct StructItem {
+    name: String,
+    fields: Vec<Field>,
+}
+
+#[derive(Default)]
+pub struct CppAccumulator {
+    use_shared_ptr: bool,
+    use_optional_serializer: bool,
+    /// Every struct and alias so far, which members must not be named after.
+    type_names: HashSet<String>,
+    /// Structs that are pointed to before they are declared.
+    forward_list: Vec<String>,
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<String>,
+}
+
+impl CppAccumulator {
+    pub fn begin() -> Self {
+        CppAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut StructItem {
+        self.struct_stack.last_mut().unwrap()
+    }
+
+    /// nlohmann cannot convert a `std::optional` by itself, so the output brings a serializer
+    /// for the ones that are not members, as in `std::vector<std::optional<T>>`.
+    fn get_type(&mut self, ty: JsonType) -> String {
+        match ty {
+            JsonType::Null => String::from("nlohmann::json"),
+            JsonType::Number(n) => String::from(Self::get_number(n)),
+            JsonType::Boolean => String::from("bool"),
+            JsonType::String | JsonType::DateTime(_) => String::from("std::string"),
+            JsonType::Object(ty) => ty,
+            JsonType::Array(ty) => format!("std::vector<{}>", self.get_type(*ty)),
+            JsonType::Nullable(ty) => match self.get_type(*ty) {
+                ty if ty == "nlohmann::json" => ty,
+                ty => {
+                    self.use_optional_serializer = true;
+                    format!("std::optional<{}>", ty)
+                }
+            },
+            JsonType::Union(_) => String::from("nlohmann::json"),
+        }
+    }
+
+    /// `nlohmann::json` can already hold `null`, so it is never made optional.
+    /// Structs that are still being declared are incomplete, so they are held by a
+    /// `std::shared_ptr` instead of a `std::optional`.
+    fn push_field(&mut self, key: &str, ty: JsonType, optional: bool) {
+        let (ty, nullable) = match ty {
+            JsonType::Nullable(ty) => (*ty, true),
+            ty => (ty, false),
+        };
+        let pointer = match &ty {
+            JsonType::Object(name) => self.struct_stack.iter().any(|s| &s.name == name),
+            _ => false,
+        };
+        let inner = self.get_type(ty);
+        if pointer && self.get_current().name != inner && !self.forward_list.contains(&inner) {
+            self.forward_list.push(inner.clone());
+        }
+        let (ty, kind) = if inner == "nlohmann::json" {
+            (inner, FieldKind::Plain)
+        } else if pointer {
+            self.use_shared_ptr = true;
+            let ty = format!("std::shared_ptr<{}>", inner);
+            match optional {
+                true => (ty, FieldKind::Optional(inner)),
+                false => (ty, FieldKind::Nullable(inner)),
+            }
+        } else if optional {
+            (
+                format!("std::optional<{}>", inner),
+                FieldKind::Optional(inner),
+            )
+        } else if nullable {
+            (
+                format!("std::optional<{}>", inner),
+                FieldKind::Nullable(inner),
+            )
+        } else {
+            (inner, FieldKind::Plain)
+        };
+
+        //  Named once the struct is done, see `pop_object_type`.
+        self.get_current().fields.push(Field {
+            name: naming::identifier(key),
+            key: String::from(key),
+            ty,
+            kind,
+            pointer,
+        });
+    }
+
+    fn to_json(s: &StructItem) -> String {
+        let mut acc = format!(
+            "inline void to_json(nlohmann::json& j, const {}& v) {{\n\tj = nlohmann::json::object();\n",
+            s.name
+        );
+        s.fields.iter().for_each(|field| {
+            let key = naming::escape(&field.key, &['"']);
+            acc += &match field.kind {
+                FieldKind::Plain => format!("\tj[\"{}\"] = v.{};\n", key, field.name),
+                FieldKind::Nullable(_) => format!(
+                    "\tj[\"{0}\"] = v.{1} ? nlohmann::json(*v.{1}) : nlohmann::json(nullptr);\n",
+                    key, field.name
+                ),
+                FieldKind::Optional(_) => format!(
+                    "\tif (v.{1}) {{\n\t\tj[\"{0}\"] = *v.{1};\n\t}}\n",
+                    key, field.name
+                ),
+            };
+        });
+        acc + "}\n\n"
+    }
+
+    fn from_json(s: &StructItem) -> String {
+        let mut acc = format!(
+            "inline void from_json(const nlohmann::json& j, {}& v) {{\n",
+            s.name
+        );
+        s.fields.iter().for_each(|field| {
+            let key = naming::escape(&field.key, &['"']);
+            let get = |inner: &str| match field.pointer {
+                true => format!("std::make_shared<{0}>(j.at(\"{1}\").get<{0}>())", inner, key),
+                false => format!("j.at(\"{}\").get<{}>()", key, inner),
+            };
+            acc += &match &field.kind {
+                FieldKind::Plain => format!("\tj.at(\"{}\").get_to(v.{});\n", key, field.name),
+                FieldKind::Nullable(inner) => format!(
+                    "\tif (!j.at(\"{}\").is_null()) {{\n\t\tv.{} = {};\n\t}}\n",
+                    key,
+                    field.name,
+                    get(inner)
+                ),
+                FieldKind::Optional(inner) => format!(
+                    "\tif (j.contains(\"{0}\") && !j.at(\"{0}\").is_null()) {{\n\t\tv.{1} = {2};\n\t}}\n",
+                    key,
+                    field.name,
+                    get(inner)
+                ),
+            };
+        });
+        acc + "}\n\n"
+    }
+
+    fn get_number(num: Number) -> &'static str {
+        match num {
+            Number::Int => "int64_t",
+            Number::Float => "double",
+        }
+    }
+}
+
+impl TypeAccumulator for CppAccumulator {
+    fn end(&mut self) -> String {
+        let mut end_str = String::new();
+
+        if !self.forward_list.is_empty() {
+            self.forward_list
+                .iter()
+                .for_each(|name| end_str += &format!("struct {};\n", name));
+            end_str += "\n";
+        }
+
+        if self.use_optional_serializer {
+            end_str += r#"namespace nlohmann {
+template <typename T>
+struct adl_serializer<std::optional<T>> {
+	static void to_json(json& j, const std::optional<T>& v) {
+		j = v ? json(*v) : json(nullptr);
+	}
+
+	static void from_json(const json& j, std::optional<T>& v) {
+		v = j.is_null() ? std::nullopt : std::optional<T>(j.get<T>());
+	}
+};
+}
+
+"#;
+        }
+
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        end_str += "//  #include <cstdint>\n";
+        if self.use_shared_ptr {
+            end_str += "//  #include <memory>\n";
+        }
+        end_str += r#"//  #include <optional>
+//  #include <string>
+//  #include <vector>
+//  #include <nlohmann/json.hpp>
+//
+//  T ty = nlohmann::json::parse(str).get<T>();
+//  std::string str = nlohmann::json(ty).dump();"#;
+
+        end_str
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        self.push_field(key, JsonType::Number(number), false);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Boolean, false);
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::String, false);
+        Ok(())
+    }
+
+    /// nlohmann has no date type, so the ISO 8601 string is kept.
+    fn date_time(&mut self, key: &str, _date_time: DateTime) -> Result<(), Error> {
+        self.string(key)
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Null, false);
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, JsonType::Array(Box::new(ty)), false);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, JsonType::Nullable(Box::new(ty)), false);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        self.push_field(key, JsonType::Union(tys), false);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, ty, true);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Object(String::from(object_name)), false);
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.type_names.insert(String::from(object_name));
+        self.struct_stack.push(StructItem {
+            name: String::from(object_name),
+            fields: vec![],
+        });
+        Ok(())
+    }
+
+    /// Structs whose fields all match their keys use `NLOHMANN_DEFINE_TYPE_NON_INTRUSIVE`.
+    /// The rest get their own `to_json` and `from_json`.
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        let mut s = self.struct_stack.pop().unwrap();
+
+        //  Every type the struct uses is known by now. A member named after one of them, or
+        //  after the struct itself, would change what the name means within the struct.
+        let mut names = HashSet::new();
+        s.fields.iter_mut().for_each(|field| {
+            field.name = naming::unique(&field.name, &mut names, |name| {
+                if KEYWORDS.contains(&name.as_str()) || self.type_names.contains(&name) {
+                    name + "_"
+                } else {
+                    name
+                }
+            });
+        });
+
+        let mut acc = format!("struct {} {{\n", s.name);
+        s.fields.iter().for_each(|field| {
+            acc += &format!("\t{} {};\n", field.ty, field.name);
+        });
+        acc += "};\n\n";
+
+        let use_macro = !s.fields.is_empty()
+            && s.fields
+                .iter()
+                .all(|field| matches!(field.kind, FieldKind::Plain) && field.name == field.key);
+        if use_macro {
+            let names = s
+                .fields
+                .iter()
+                .map(|field| field.name.as_str())
+                .collect::<Vec<_>>();
+            acc += &format!(
+                "NLOHMANN_DEFINE_TYPE_NON_INTRUSIVE({}, {})\n\n",
+                s.name,
+                names.join(", ")
+            );
+        } else {
+            acc += &Self::to_json(&s);
+            acc += &Self::from_json(&s);
+        }
+        self.done_list.push(acc);
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        self.type_names.insert(String::from(name));
+        let ty = self.get_type(ty);
+        self.done_list.push(format!("using {} = {};\n\n", name, ty));
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+}
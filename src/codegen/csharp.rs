@@ -0,0 +1,206 @@
+use super::*;
+
+struct StructItem {
+    acc: String,
+    name: String,
+    /// Property names taken so far.
+    names: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct CSharpAccumulator {
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<String>,
+}
+
+impl CSharpAccumulator {
+    pub fn begin() -> Self {
+        CSharpAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut StructItem {
+        self.struct_stack.last_mut().unwrap()
+    }
+
+    fn get_type(ty: JsonType) -> String {
+        match ty {
+            JsonType::Null => String::from("object?"),
+            JsonType::Number(n) => String::from(Self::get_number(n)),
+            JsonType::Boolean => String::from("bool"),
+            JsonType::String => String::from("string"),
+            JsonType::DateTime(DateTime::Offset) => String::from("DateTimeOffset"),
+            JsonType::DateTime(DateTime::Local) => String::from("DateTime"),
+            //  `System.Text.Json` reads these since .NET 7.
+            JsonType::DateTime(DateTime::Date) => String::from("DateOnly"),
+            JsonType::DateTime(DateTime::Time) => String::from("TimeOnly"),
+            JsonType::Object(ty) => ty,
+            JsonType::Array(ty) => format!("List<{}>", Self::get_type(*ty)),
+            JsonType::Nullable(ty) => Self::get_nullable_type(*ty),
+            JsonType::Union(_) => String::from("object"),
+        }
+    }
+
+    fn get_nullable_type(ty: JsonType) -> String {
+        match ty {
+            JsonType::Null | JsonType::Nullable(_) => Self::get_type(ty),
+            ty => format!("{}?", Self::get_type(ty)),
+        }
+    }
+
+    /// Properties are PascalCase, so the attribute keeps the key.
+    /// Missing keys are the only ones that are not `required`.
+    fn push_property(&mut self, key: &str, ty: &str, required: bool) {
+        let acc = self.get_current();
+        let mut property = naming::pascal_case(key);
+        if !property.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            property = String::from("_") + &property;
+        }
+        //  Members cannot share the name of their type.
+        if property == acc.name {
+            property += "Value";
+        }
+        let property = naming::unique(&property, &mut acc.names, |property| property);
+        if property != key {
+            acc.acc += &format!(
+                "\t[JsonPropertyName(\"{}\")]\n",
+                naming::escape(key, &['"'])
+            );
+        }
+        acc.acc += &format!(
+            "\tpublic {}{} {} {{ get; init; }}\n",
+            if required { "required " } else { "" },
+            ty,
+            property
+        );
+    }
+
+    fn get_number(num: Number) -> &'static str {
+        match num {
+            Number::Int => "long",
+            Number::Float => "double",
+        }
+    }
+}
+
+impl TypeAccumulator for CSharpAccumulator {
+    fn end(&mut self) -> String {
+        let mut end_str = String::new();
+
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        end_str += r#"//  #nullable enable
+//  using System.Collections.Generic;
+//  using System.Text.Json;
+//  using System.Text.Json.Serialization;
+//
+//  var json = JsonSerializer.Serialize(ty);
+//  var ty = JsonSerializer.Deserialize<T>(json);"#;
+
+        end_str
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        self.push_property(key, Self::get_number(number), true);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_property(key, "bool", true);
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        self.push_property(key, "string", true);
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        let ty = Self::get_type(JsonType::DateTime(date_time));
+        self.push_property(key, &ty, true);
+        Ok(())
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        self.push_property(key, "object?", true);
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = Self::get_type(ty);
+        self.push_property(key, &format!("List<{}>", ty), true);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = Self::get_nullable_type(ty);
+        self.push_property(key, &ty, true);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, _: Vec<JsonType>) -> Result<(), Error> {
+        self.push_property(key, "object", true);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = Self::get_nullable_type(ty);
+        self.push_property(key, &ty, false);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        self.push_property(key, object_name, true);
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.struct_stack.push(StructItem {
+            acc: String::new(),
+            name: String::from(object_name),
+            names: HashSet::new(),
+        });
+        let acc = self.get_current();
+        acc.acc += &format!("public record {}\n{{\n", object_name);
+        Ok(())
+    }
+
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        let acc = self.get_current();
+        acc.acc += "}\n\n";
+        let s = self.struct_stack.pop().unwrap();
+        self.done_list.push(s.acc);
+        Ok(())
+    }
+
+    /// C# has no type aliases outside of `using`, so arrays are subclassed and everything else
+    /// is wrapped.
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let alias = match ty {
+            JsonType::Array(_) => {
+                format!("public class {} : {} {{}}\n\n", name, Self::get_type(ty))
+            }
+            ty => format!(
+                "public record {}\n{{\n\tpublic required {} Value {{ get; init; }}\n}}\n\n",
+                name,
+                Self::get_type(ty)
+            ),
+        };
+        self.done_list.push(alias);
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &[
+            "List",
+            "DateTimeOffset",
+            "DateTime",
+            "DateOnly",
+            "TimeOnly",
+            "JsonPropertyName",
+        ]
+    }
+}
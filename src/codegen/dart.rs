@@ -1,5 +1,12 @@
 use super::*;
 
+const KEYWORDS: &[&str] = &[
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
 pub struct ArrayField {
     very_inner: String,
     depth: usize,
@@ -19,16 +26,21 @@ enum FieldType {
     Primitive,
     Struct,
     Array(ArrayField),
+    Optional(Box<FieldType>),
 }
 
 struct StructItem {
     acc: String,
     name: String,
-    fields: Vec<(String, FieldType)>,
+    /// Field names, the keys they came from and their types.
+    fields: Vec<(String, String, FieldType)>,
+    /// Field names taken so far.
+    names: HashSet<String>,
 }
 
 #[derive(Default)]
 pub struct DartAccumulator {
+    camel_case: bool,
     struct_stack: Vec<StructItem>,
     done_list: Vec<String>,
 }
@@ -43,7 +55,10 @@ impl DartAccumulator {
     }
 
     fn is_builtin_type(s: &str) -> bool {
-        matches!(s, "dynamic" | "bool" | "String" | "double" | "int")
+        matches!(
+            s.trim_end_matches('?'),
+            "dynamic" | "bool" | "String" | "double" | "int"
+        )
     }
 
     fn get_type(&mut self, ty: JsonType) -> String {
@@ -51,9 +66,14 @@ impl DartAccumulator {
             JsonType::Null => String::from("dynamic"),
             JsonType::Number(n) => String::from(self.get_number(n)),
             JsonType::Boolean => String::from("bool"),
-            JsonType::String => String::from("String"),
+            JsonType::String | JsonType::DateTime(_) => String::from("String"),
             JsonType::Object(ty) => ty,
             JsonType::Array(ty) => format!("List<{}>", self.get_type(*ty)),
+            JsonType::Nullable(ty) => match self.get_type(*ty) {
+                ty if ty == "dynamic" => ty,
+                ty => format!("{}?", ty),
+            },
+            JsonType::Union(_) => String::from("dynamic"),
         }
     }
 
@@ -62,9 +82,120 @@ impl DartAccumulator {
             JsonType::Null => String::from("dynamic"),
             JsonType::Number(n) => String::from(self.get_number(n)),
             JsonType::Boolean => String::from("bool"),
-            JsonType::String => String::from("String"),
+            JsonType::String | JsonType::DateTime(_) => String::from("String"),
             JsonType::Object(ty) => ty,
             JsonType::Array(ty) => self.get_without_outer_list(*ty),
+            JsonType::Nullable(ty) => format!("{}?", self.get_without_outer_list(*ty)),
+            JsonType::Union(_) => String::from("dynamic"),
+        }
+    }
+
+    /// Fields that differ from their keys are still read and written under their keys.
+    fn push_field(&mut self, key: &str, ty: &str, field_ty: FieldType) {
+        let ident = if self.camel_case {
+            naming::identifier(&naming::camel_case(key))
+        } else {
+            naming::identifier(key)
+        };
+        let acc = self.get_current();
+        let field = naming::unique(&ident, &mut acc.names, |ident| {
+            if KEYWORDS.contains(&ident.as_str()) {
+                ident + "_"
+            } else {
+                ident
+            }
+        });
+        acc.acc += &format!("\tfinal {} {};\n", ty, field);
+        acc.fields.push((field, String::from(key), field_ty));
+    }
+
+    fn get_field_type(&mut self, ty: JsonType) -> FieldType {
+        match ty {
+            JsonType::Object(_) => FieldType::Struct,
+            JsonType::Array(ty) => FieldType::Array(ArrayField {
+                very_inner: self.get_without_outer_list(*ty.clone()),
+                depth: ArrayField::depth_from_ty(&ty, 1),
+            }),
+            _ => FieldType::Primitive,
+        }
+    }
+
+    /// `json` is the expression holding the field's json value.
+    fn from_json(json: &str, field_ty: &FieldType) -> String {
+        match field_ty {
+            FieldType::Struct | FieldType::Primitive => json.to_owned(),
+            FieldType::Array(s) => {
+                fn list_from_depth(name: &str, depth: usize) -> String {
+                    let mut acc = String::from(name);
+                    (0..depth + 1).for_each(|_| {
+                        acc = format!("List<{}>", acc);
+                    });
+                    acc
+                }
+                let mut iname = String::from("i");
+                let mut acc = if Self::is_builtin_type(&s.very_inner) {
+                    iname.clone()
+                } else if let Some(very_inner) = s.very_inner.strip_suffix('?') {
+                    format!("{0} == null ? null : {1}.fromJson({0})", iname, very_inner)
+                } else {
+                    format!("{}.fromJson({})", s.very_inner, iname)
+                };
+                for depth in 0..s.depth {
+                    let next_iname = if depth == s.depth - 1 {
+                        json.to_owned()
+                    } else {
+                        format!("i{}", depth)
+                    };
+
+                    acc = format!(
+                        "{}.from({}.map(({}) => {}))",
+                        list_from_depth(&s.very_inner, depth),
+                        next_iname,
+                        iname,
+                        acc
+                    );
+                    iname = next_iname;
+                }
+                acc
+            }
+            FieldType::Optional(ty) => match **ty {
+                FieldType::Array(_) => {
+                    format!("{} == null ? null : {}", json, Self::from_json(json, ty))
+                }
+                _ => Self::from_json(json, ty),
+            },
+        }
+    }
+
+    /// `field` is the expression holding the field's dart value.
+    fn to_json(field: &str, field_ty: &FieldType) -> String {
+        match field_ty {
+            FieldType::Struct => field.to_owned() + ".toJson()",
+            FieldType::Primitive => field.to_owned(),
+            FieldType::Array(s) => {
+                let mut iname = String::from("i");
+                let mut acc = if Self::is_builtin_type(&s.very_inner) {
+                    iname.clone()
+                } else if s.very_inner.ends_with('?') {
+                    iname.clone() + "?.toJson()"
+                } else {
+                    iname.clone() + ".toJson()"
+                };
+                for depth in 0..s.depth {
+                    let next_iname = if depth == s.depth - 1 {
+                        field.to_owned()
+                    } else {
+                        format!("i{}", depth)
+                    };
+                    acc = format!("{}.map(({}) => {}).toList()", next_iname, iname, acc);
+                    iname = next_iname;
+                }
+                acc
+            }
+            FieldType::Optional(ty) => match **ty {
+                FieldType::Primitive => Self::to_json(field, ty),
+                _ => Self::to_json(&format!("{}?", field), ty),
+            },
         }
     }
 
@@ -92,53 +223,60 @@ impl TypeAccumulator for DartAccumulator {
 
     fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
         let num_ty = self.get_number(number);
-        let acc = self.get_current();
-        acc.acc += &format!("\tfinal {} {};\n", num_ty, key);
-        acc.fields.push((String::from(key), FieldType::Primitive));
+        self.push_field(key, num_ty, FieldType::Primitive);
         Ok(())
     }
 
     fn boolean(&mut self, key: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        acc.acc += &format!("\tfinal bool {};\n", key);
-        acc.fields.push((String::from(key), FieldType::Primitive));
+        self.push_field(key, "bool", FieldType::Primitive);
         Ok(())
     }
 
     fn string(&mut self, key: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        acc.acc += &format!("\tfinal String {};\n", key);
-        acc.fields.push((String::from(key), FieldType::Primitive));
+        self.push_field(key, "String", FieldType::Primitive);
         Ok(())
     }
 
+    /// Left as the ISO 8601 string that `DateTime.parse` reads, unless it is a time alone.
+    fn date_time(&mut self, key: &str, _date_time: DateTime) -> Result<(), Error> {
+        self.string(key)
+    }
+
     fn unknown(&mut self, key: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        acc.acc += &format!("\tfinal dynamic {};\n", key);
-        acc.fields.push((String::from(key), FieldType::Primitive));
+        self.push_field(key, "dynamic", FieldType::Primitive);
         Ok(())
     }
 
     fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
-        let ty_str = self.get_type(ty.clone());
-        let ty_without_outer = self.get_without_outer_list(ty.clone());
-        let acc = self.get_current();
-        let ty_name = format!("List<{}>", ty_str);
-        acc.acc += &format!("\tfinal {} {};\n", ty_name, key);
-        acc.fields.push((
-            String::from(key),
-            FieldType::Array(ArrayField {
-                very_inner: ty_without_outer,
-                depth: ArrayField::depth_from_ty(&ty, 1),
-            }),
-        ));
+        let ty = JsonType::Array(Box::new(ty));
+        let ty_name = self.get_type(ty.clone());
+        let field_ty = self.get_field_type(ty);
+        self.push_field(key, &ty_name, field_ty);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty_name = self.get_type(JsonType::Nullable(Box::new(ty.clone())));
+        let field_ty = self.get_field_type(ty);
+        self.push_field(key, &ty_name, FieldType::Optional(Box::new(field_ty)));
         Ok(())
     }
 
+    fn union(&mut self, key: &str, _: Vec<JsonType>) -> Result<(), Error> {
+        self.unknown(key)
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        //  Missing and `null` are the same thing here.
+        match ty {
+            JsonType::Null => self.unknown(key),
+            JsonType::Nullable(ty) => self.nullable(key, *ty),
+            ty => self.nullable(key, ty),
+        }
+    }
+
     fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        acc.acc += &format!("\tfinal {} {};\n", object_name, key);
-        acc.fields.push((String::from(key), FieldType::Struct));
+        self.push_field(key, object_name, FieldType::Struct);
         Ok(())
     }
 
@@ -147,6 +285,7 @@ impl TypeAccumulator for DartAccumulator {
             acc: String::new(),
             name: String::from(object_name),
             fields: vec![],
+            names: HashSet::new(),
         });
         let acc = self.get_current();
         acc.acc += &format!("class {} {{\n", object_name);
@@ -156,78 +295,21 @@ impl TypeAccumulator for DartAccumulator {
     fn pop_object_type(&mut self) -> Result<(), Error> {
         let acc = self.get_current();
         acc.acc += &format!("\t{}.fromJson(Map<String, dynamic> json):\n", acc.name);
-        acc.fields.iter().for_each(|(field, field_ty)| {
+        acc.fields.iter().for_each(|(field, key, field_ty)| {
+            let key = naming::escape(key, &['\'', '$']);
             acc.acc += &format!(
                 "\t\t{} = {},\n",
                 field,
-                match field_ty {
-                    FieldType::Struct | FieldType::Primitive => format!("json['{}']", field),
-                    FieldType::Array(s) => {
-                        fn list_from_depth(name: &str, depth: usize) -> String {
-                            let mut acc = String::from(name);
-                            (0..depth + 1).for_each(|_| {
-                                acc = format!("List<{}>", acc);
-                            });
-                            acc
-                        }
-                        let mut iname = String::from("i");
-                        let mut acc = if Self::is_builtin_type(&s.very_inner) {
-                            iname.clone()
-                        } else {
-                            format!("{}.fromJson({})", s.very_inner, iname)
-                        };
-                        for depth in 0..s.depth {
-                            let next_iname = if depth == s.depth - 1 {
-                                format!("json['{}']", field)
-                            } else {
-                                format!("i{}", depth)
-                            };
-
-                            acc = format!(
-                                "{}.from({}.map(({}) => {}))",
-                                list_from_depth(&s.very_inner, depth),
-                                next_iname,
-                                iname,
-                                acc
-                            );
-                            iname = next_iname;
-                        }
-                        acc
-                    }
-                }
+                Self::from_json(&format!("json['{}']", key), field_ty)
             );
         });
         acc.acc.pop();
         acc.acc.pop();
         acc.acc += ";\n\n";
         acc.acc += "\tMap<String, dynamic> toJson() => {";
-        acc.fields.iter().for_each(|(field, field_ty)| {
-            acc.acc += &format!(
-                "\n\t\t'{}': {},",
-                field,
-                &match field_ty {
-                    FieldType::Struct => field.to_owned() + ".toJson()",
-                    FieldType::Primitive => field.to_owned(),
-                    FieldType::Array(s) => {
-                        let mut iname = String::from("i");
-                        let mut acc = if Self::is_builtin_type(&s.very_inner) {
-                            iname.clone()
-                        } else {
-                            iname.clone() + ".toJson()"
-                        };
-                        for depth in 0..s.depth {
-                            let next_iname = if depth == s.depth - 1 {
-                                field.to_owned()
-                            } else {
-                                format!("i{}", depth)
-                            };
-                            acc = format!("{}.map(({}) => {}).toList()", next_iname, iname, acc);
-                            iname = next_iname;
-                        }
-                        acc
-                    }
-                }
-            );
+        acc.fields.iter().for_each(|(field, key, field_ty)| {
+            let key = naming::escape(key, &['\'', '$']);
+            acc.acc += &format!("\n\t\t'{}': {},", key, Self::to_json(field, field_ty));
         });
         acc.acc += "\n\t};\n";
         acc.acc += "}\n\n";
@@ -236,7 +318,22 @@ impl TypeAccumulator for DartAccumulator {
         Ok(())
     }
 
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.done_list
+            .push(format!("typedef {} = {};\n\n", name, ty));
+        Ok(())
+    }
+
     fn prefered_object_name(&self) -> String {
         String::from("_Type")
     }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &["String", "List", "Map"]
+    }
+
+    fn use_idiomatic_casing(&mut self) {
+        self.camel_case = true;
+    }
 }
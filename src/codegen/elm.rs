@@ -0,0 +1,372 @@
+use super::*;
+
+const KEYWORDS: &[&str] = &[
+    "alias",
+    "as",
+    "case",
+    "command",
+    "effect",
+    "else",
+    "exposing",
+    "if",
+    "import",
+    "in",
+    "infix",
+    "let",
+    "module",
+    "of",
+    "port",
+    "subscription",
+    "then",
+    "type",
+    "where",
+];
+
+struct Field {
+    name: String,
+    key: String,
+    ty: JsonType,
+    /// Missing from some objects.
+    optional: bool,
+}
+
+struct StructItem {
+    name: String,
+    fields: Vec<Field>,
+    /// Field names taken so far.
+    names: HashSet<String>,
+    /// Referred to before it was done, see [`ElmAccumulator::get_decoder`].
+    recursive: bool,
+}
+
+#[derive(Default)]
+pub struct ElmAccumulator {
+    use_encode_maybe: bool,
+    use_optional_field: bool,
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<String>,
+}
+
+impl ElmAccumulator {
+    pub fn begin() -> Self {
+        ElmAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut StructItem {
+        self.struct_stack.last_mut().unwrap()
+    }
+
+    /// Wrap `s` in parentheses if it is more than one word.
+    fn group(s: String) -> String {
+        if s.contains(' ') {
+            format!("({})", s)
+        } else {
+            s
+        }
+    }
+
+    fn get_type(ty: &JsonType) -> String {
+        match ty {
+            JsonType::Null | JsonType::Union(_) => String::from("Decode.Value"),
+            JsonType::Number(Number::Int) => String::from("Int"),
+            JsonType::Number(Number::Float) => String::from("Float"),
+            JsonType::Boolean => String::from("Bool"),
+            JsonType::String | JsonType::DateTime(_) => String::from("String"),
+            JsonType::Object(ty) => ty.clone(),
+            JsonType::Array(ty) => format!("List {}", Self::group(Self::get_type(ty))),
+            JsonType::Nullable(ty) => format!("Maybe {}", Self::group(Self::get_type(ty))),
+        }
+    }
+
+    /// Nested lists nest their decoders, as in `Decode.list (Decode.list decoderItem)`.
+    /// Decoders of types that are still being declared are not defined yet, so they are
+    /// referred to lazily, and their types are wrapped since type aliases cannot recurse.
+    fn get_decoder(&mut self, ty: &JsonType) -> String {
+        match ty {
+            JsonType::Null | JsonType::Union(_) => String::from("Decode.value"),
+            JsonType::Number(Number::Int) => String::from("Decode.int"),
+            JsonType::Number(Number::Float) => String::from("Decode.float"),
+            JsonType::Boolean => String::from("Decode.bool"),
+            JsonType::String | JsonType::DateTime(_) => String::from("Decode.string"),
+            JsonType::Object(ty) => match self.struct_stack.iter_mut().find(|s| &s.name == ty) {
+                Some(s) => {
+                    s.recursive = true;
+                    format!("Decode.lazy (\\_ -> decoder{})", ty)
+                }
+                None => format!("decoder{}", ty),
+            },
+            JsonType::Array(ty) => format!("Decode.list {}", Self::group(self.get_decoder(ty))),
+            JsonType::Nullable(ty) => {
+                format!("Decode.nullable {}", Self::group(self.get_decoder(ty)))
+            }
+        }
+    }
+
+    fn get_encoder(&mut self, ty: &JsonType) -> String {
+        match ty {
+            JsonType::Null | JsonType::Union(_) => String::from("identity"),
+            JsonType::Number(Number::Int) => String::from("Encode.int"),
+            JsonType::Number(Number::Float) => String::from("Encode.float"),
+            JsonType::Boolean => String::from("Encode.bool"),
+            JsonType::String | JsonType::DateTime(_) => String::from("Encode.string"),
+            JsonType::Object(ty) => format!("encode{}", ty),
+            JsonType::Array(ty) => {
+                format!("Encode.list {}", Self::group(self.get_encoder(ty)))
+            }
+            JsonType::Nullable(ty) => {
+                self.use_encode_maybe = true;
+                format!("encodeMaybe {}", Self::group(self.get_encoder(ty)))
+            }
+        }
+    }
+
+    /// Record fields must start with a lowercase letter.
+    fn push_field(&mut self, key: &str, ty: JsonType, optional: bool) {
+        //  Missing and `null` are the same thing here.
+        let ty = match ty {
+            JsonType::Nullable(ty) if optional => *ty,
+            ty => ty,
+        };
+        let mut name = naming::camel_case(key);
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            name = String::from("field") + &naming::pascal_case(key);
+        }
+        let s = self.get_current();
+        let name = naming::unique(&name, &mut s.names, |name| {
+            if KEYWORDS.contains(&name.as_str()) {
+                name + "_"
+            } else {
+                name
+            }
+        });
+        s.fields.push(Field {
+            name,
+            key: String::from(key),
+            ty,
+            optional,
+        });
+    }
+
+    /// The declaration, decoder and encoder for `name`.
+    fn declare(name: &str, ty: &str, decoder: &str, encoder: &str) -> String {
+        format!(
+            "type alias {0} =\n    {1}\n\n\ndecoder{0} : Decoder {0}\ndecoder{0} =\n    {2}\n\n\nencode{0} : {0} -> Encode.Value\nencode{0} value =\n    {3}\n\n\n",
+            name, ty, decoder, encoder
+        )
+    }
+
+    /// Like [`ElmAccumulator::declare`], but the record is wrapped in a custom type of the same name.
+    fn declare_recursive(name: &str, ty: &str, decoder: &str, encoder: &str) -> String {
+        format!(
+            "type {0}\n    = {0}\n        {1}\n\n\ndecoder{0} : Decoder {0}\ndecoder{0} =\n    {2}\n\n\nencode{0} : {0} -> Encode.Value\nencode{0} ({0} value) =\n    {3}\n\n\n",
+            name,
+            ty.replace("\n", "\n    "),
+            decoder,
+            encoder
+        )
+    }
+}
+
+impl TypeAccumulator for ElmAccumulator {
+    fn end(&mut self) -> String {
+        let mut end_str = String::from(
+            r#"import Json.Decode as Decode exposing (Decoder)
+import Json.Encode as Encode
+
+
+andMap : Decoder a -> Decoder (a -> b) -> Decoder b
+andMap =
+    Decode.map2 (|>)
+
+
+"#,
+        );
+
+        if self.use_optional_field {
+            end_str += r#"optionalField : String -> Decoder a -> Decoder (Maybe a)
+optionalField key decoder =
+    Decode.maybe (Decode.field key Decode.value)
+        |> Decode.andThen
+            (\field ->
+                case field of
+                    Nothing ->
+                        Decode.succeed Nothing
+
+                    Just _ ->
+                        Decode.field key (Decode.nullable decoder)
+            )
+
+
+"#;
+        }
+
+        if self.use_encode_maybe {
+            end_str += r#"encodeMaybe : (a -> Encode.Value) -> Maybe a -> Encode.Value
+encodeMaybe encoder =
+    Maybe.map encoder >> Maybe.withDefault Encode.null
+
+
+"#;
+        }
+
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        end_str
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        self.push_field(key, JsonType::Number(number), false);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Boolean, false);
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::String, false);
+        Ok(())
+    }
+
+    /// `elm/json` has no date decoder, so the ISO 8601 string is kept.
+    fn date_time(&mut self, key: &str, _date_time: DateTime) -> Result<(), Error> {
+        self.string(key)
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Null, false);
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, JsonType::Array(Box::new(ty)), false);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, JsonType::Nullable(Box::new(ty)), false);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        self.push_field(key, JsonType::Union(tys), false);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, ty, true);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Object(String::from(object_name)), false);
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.struct_stack.push(StructItem {
+            name: String::from(object_name),
+            fields: vec![],
+            names: HashSet::new(),
+            recursive: false,
+        });
+        Ok(())
+    }
+
+    /// Decoders apply the record constructor one field at a time with `andMap`.
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        //  Decoders are found while the type is still on the stack, so it can refer to itself.
+        let fields = std::mem::take(&mut self.get_current().fields);
+        let decoders = fields
+            .iter()
+            .map(|field| self.get_decoder(&field.ty))
+            .collect::<Vec<_>>();
+        let mut s = self.struct_stack.pop().unwrap();
+        s.fields = fields;
+        if s.fields.is_empty() {
+            let acc = Self::declare(&s.name, "{}", "Decode.succeed {}", "Encode.object []");
+            self.done_list.push(acc);
+            return Ok(());
+        }
+
+        let mut ty = vec![];
+        let mut decoder = if s.recursive {
+            let args = (1..=s.fields.len())
+                .map(|i| format!("field{}", i))
+                .collect::<Vec<_>>();
+            let record = s
+                .fields
+                .iter()
+                .zip(&args)
+                .map(|(field, arg)| format!("{} = {}", field.name, arg))
+                .collect::<Vec<_>>();
+            format!(
+                "Decode.succeed (\\{} -> {} {{ {} }})",
+                args.join(" "),
+                s.name,
+                record.join(", ")
+            )
+        } else {
+            format!("Decode.succeed {}", s.name)
+        };
+        let mut encoder = vec![];
+        for (field, field_decoder) in s.fields.iter().zip(decoders) {
+            let key = naming::escape(&field.key, &['"']);
+            let field_ty = match field.optional {
+                true => JsonType::Nullable(Box::new(field.ty.clone())),
+                false => field.ty.clone(),
+            };
+            ty.push(format!("{} : {}", field.name, Self::get_type(&field_ty)));
+            decoder += &if field.optional {
+                self.use_optional_field = true;
+                format!(
+                    "\n        |> andMap (optionalField \"{}\" {})",
+                    key,
+                    Self::group(field_decoder)
+                )
+            } else {
+                format!(
+                    "\n        |> andMap (Decode.field \"{}\" {})",
+                    key,
+                    Self::group(field_decoder)
+                )
+            };
+            encoder.push(format!(
+                "( \"{}\", {} value.{} )",
+                key,
+                self.get_encoder(&field_ty),
+                field.name
+            ));
+        }
+
+        let ty = format!("{{ {}\n    }}", ty.join("\n    , "));
+        let encoder = format!(
+            "Encode.object\n        [ {}\n        ]",
+            encoder.join("\n        , ")
+        );
+        let acc = if s.recursive {
+            Self::declare_recursive(&s.name, &ty, &decoder, &encoder)
+        } else {
+            Self::declare(&s.name, &ty, &decoder, &encoder)
+        };
+        self.done_list.push(acc);
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let encoder = format!("{} value", self.get_encoder(&ty));
+        let decoder = self.get_decoder(&ty);
+        let acc = Self::declare(name, &Self::get_type(&ty), &decoder, &encoder);
+        self.done_list.push(acc);
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &["Int", "Float", "Bool", "String", "List", "Maybe", "Decoder"]
+    }
+}
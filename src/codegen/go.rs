@@ -0,0 +1,196 @@
+use super::*;
+
+struct StructItem {
+    name: String,
+    acc: String,
+    /// Field names taken so far.
+    names: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct GoAccumulator {
+    use_time: bool,
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<String>,
+}
+
+impl GoAccumulator {
+    pub fn begin() -> Self {
+        GoAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut String {
+        &mut self.struct_stack.last_mut().unwrap().acc
+    }
+
+    fn get_type(&mut self, ty: JsonType) -> String {
+        match ty {
+            JsonType::Null => String::from("interface{}"),
+            JsonType::Number(n) => String::from(Self::get_number(n)),
+            JsonType::Boolean => String::from("bool"),
+            JsonType::String => String::from("string"),
+            JsonType::DateTime(DateTime::Offset) => {
+                self.use_time = true;
+                String::from("time.Time")
+            }
+            //  `time.Time` only reads RFC 3339, which needs a date, a time and an offset.
+            JsonType::DateTime(_) => String::from("string"),
+            JsonType::Object(ty) => self.get_object(ty),
+            JsonType::Array(ty) => match *ty {
+                //  Slices can already hold the struct they are in.
+                JsonType::Object(ty) => format!("[]{}", ty),
+                ty => format!("[]{}", self.get_type(ty)),
+            },
+            JsonType::Nullable(ty) => self.get_pointer_type(*ty),
+            JsonType::Union(_) => String::from("interface{}"),
+        }
+    }
+
+    /// Slices and interfaces can already be `nil`, everything else needs a pointer.
+    fn get_pointer_type(&mut self, ty: JsonType) -> String {
+        match ty {
+            JsonType::Null | JsonType::Array(_) | JsonType::Nullable(_) | JsonType::Union(_) => {
+                self.get_type(ty)
+            }
+            JsonType::Object(ty) => format!("*{}", ty),
+            ty => format!("*{}", self.get_type(ty)),
+        }
+    }
+
+    /// A struct that contains itself, directly or not, would have an infinite size,
+    /// so structs that are still being declared are pointed to.
+    fn get_object(&self, name: String) -> String {
+        if self.struct_stack.iter().any(|s| s.name == name) {
+            format!("*{}", name)
+        } else {
+            name
+        }
+    }
+
+    /// Fields must be exported for `encoding/json` to see them, so the tag keeps the key.
+    fn push_field(&mut self, key: &str, ty: &str, omitempty: bool) {
+        let mut field = naming::pascal_case(key);
+        if !field.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            field = String::from("X") + &field;
+        }
+        let s = self.struct_stack.last_mut().unwrap();
+        let field = naming::unique(&field, &mut s.names, |field| field);
+        s.acc += &format!(
+            "\t{} {} `json:\"{}{}\"`\n",
+            field,
+            ty,
+            naming::escape(key, &['"']),
+            if omitempty { ",omitempty" } else { "" }
+        );
+    }
+
+    fn get_number(num: Number) -> &'static str {
+        match num {
+            Number::Int => "int64",
+            Number::Float => "float64",
+        }
+    }
+}
+
+impl TypeAccumulator for GoAccumulator {
+    fn end(&mut self) -> String {
+        let mut end_str = String::new();
+
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        if self.use_time {
+            end_str += "//  import \"time\"\n";
+        }
+        end_str += r#"//  import "encoding/json"
+//
+//  var ty T
+//  err := json.Unmarshal(data, &ty)
+//  data, err := json.Marshal(ty)"#;
+
+        end_str
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        self.push_field(key, Self::get_number(number), false);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, "bool", false);
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, "string", false);
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::DateTime(date_time));
+        self.push_field(key, &ty, false);
+        Ok(())
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, "interface{}", false);
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::Array(Box::new(ty)));
+        self.push_field(key, &ty, false);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_pointer_type(ty);
+        self.push_field(key, &ty, false);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, _: Vec<JsonType>) -> Result<(), Error> {
+        self.push_field(key, "interface{}", false);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_pointer_type(ty);
+        self.push_field(key, &ty, true);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        let ty = self.get_object(String::from(object_name));
+        self.push_field(key, &ty, false);
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.struct_stack.push(StructItem {
+            name: String::from(object_name),
+            acc: String::new(),
+            names: HashSet::new(),
+        });
+        let acc = self.get_current();
+        *acc += &format!("type {} struct {{\n", object_name);
+        Ok(())
+    }
+
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        let acc = self.get_current();
+        *acc += "}\n\n";
+        let s = self.struct_stack.pop().unwrap();
+        self.done_list.push(s.acc);
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.done_list.push(format!("type {} {}\n\n", name, ty));
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+}
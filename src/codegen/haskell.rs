@@ -0,0 +1,260 @@
+use super::*;
+
+struct Field {
+    name: String,
+    key: String,
+    ty: String,
+}
+
+struct StructItem {
+    name: String,
+    fields: Vec<Field>,
+    /// Field names taken so far.
+    names: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct HaskellAccumulator {
+    use_text: bool,
+    /// Types used from `Data.Time`.
+    time_list: Vec<&'static str>,
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<String>,
+}
+
+impl HaskellAccumulator {
+    pub fn begin() -> Self {
+        HaskellAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut StructItem {
+        self.struct_stack.last_mut().unwrap()
+    }
+
+    /// Wrap `s` in parentheses if it is more than one word.
+    fn group(s: String) -> String {
+        if s.contains(' ') {
+            format!("({})", s)
+        } else {
+            s
+        }
+    }
+
+    fn get_type(&mut self, ty: &JsonType) -> String {
+        match ty {
+            JsonType::Null | JsonType::Union(_) => String::from("Value"),
+            JsonType::Number(Number::Int) => String::from("Int"),
+            JsonType::Number(Number::Float) => String::from("Double"),
+            JsonType::Boolean => String::from("Bool"),
+            JsonType::String => {
+                self.use_text = true;
+                String::from("Text")
+            }
+            JsonType::DateTime(date_time) => {
+                let ty = match date_time {
+                    DateTime::Offset => "UTCTime",
+                    DateTime::Local => "LocalTime",
+                    DateTime::Date => "Day",
+                    DateTime::Time => "TimeOfDay",
+                };
+                if !self.time_list.contains(&ty) {
+                    self.time_list.push(ty);
+                }
+                String::from(ty)
+            }
+            JsonType::Object(ty) => ty.clone(),
+            JsonType::Array(ty) => format!("[{}]", self.get_type(ty)),
+            JsonType::Nullable(ty) => {
+                let ty = self.get_type(ty);
+                format!("Maybe {}", Self::group(ty))
+            }
+        }
+    }
+
+    /// Record fields share one namespace, so each is prefixed with its type's name, as in
+    /// `personFirstName`.
+    fn push_field(&mut self, key: &str, ty: String) {
+        let acc = self.get_current();
+        let mut suffix = naming::pascal_case(key);
+        if suffix.is_empty() {
+            suffix = String::from("Field");
+        }
+        let name = naming::unique(
+            &(naming::camel_case(&acc.name) + &suffix),
+            &mut acc.names,
+            |name| name,
+        );
+        acc.fields.push(Field {
+            name,
+            key: String::from(key),
+            ty,
+        });
+    }
+}
+
+impl TypeAccumulator for HaskellAccumulator {
+    fn end(&mut self) -> String {
+        let mut end_str = String::from("{-# LANGUAGE DeriveGeneric #-}\n\n");
+
+        end_str += "import Data.Aeson\n";
+        end_str += "import Data.Maybe (fromMaybe)\n";
+        if self.use_text {
+            end_str += "import Data.Text (Text)\n";
+        }
+        if !self.time_list.is_empty() {
+            self.time_list.sort();
+            end_str += &format!("import Data.Time ({})\n", self.time_list.join(", "));
+        }
+        end_str += "import GHC.Generics (Generic)\n\n";
+
+        end_str += r#"-- | Maps record fields back to their JSON keys.
+jsonOptions :: [(String, String)] -> Options
+jsonOptions keys =
+  defaultOptions {fieldLabelModifier = \field -> fromMaybe field (lookup field keys)}
+
+"#;
+
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        end_str
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        let ty = self.get_type(&JsonType::Number(number));
+        self.push_field(key, ty);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, String::from("Bool"));
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        let ty = self.get_type(&JsonType::String);
+        self.push_field(key, ty);
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        let ty = self.get_type(&JsonType::DateTime(date_time));
+        self.push_field(key, ty);
+        Ok(())
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        let ty = self.get_type(&JsonType::Null);
+        self.push_field(key, ty);
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(&JsonType::Array(Box::new(ty)));
+        self.push_field(key, ty);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(&JsonType::Nullable(Box::new(ty)));
+        self.push_field(key, ty);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        let ty = self.get_type(&JsonType::Union(tys));
+        self.push_field(key, ty);
+        Ok(())
+    }
+
+    /// Aeson decodes a missing key as `Nothing`.
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = match ty {
+            JsonType::Nullable(_) => self.get_type(&ty),
+            ty => self.get_type(&JsonType::Nullable(Box::new(ty))),
+        };
+        self.push_field(key, ty);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        self.push_field(key, String::from(object_name));
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.struct_stack.push(StructItem {
+            name: String::from(object_name),
+            fields: vec![],
+            names: HashSet::new(),
+        });
+        Ok(())
+    }
+
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        let s = self.struct_stack.pop().unwrap();
+
+        //  Generic instances of a constructor without fields do not expect an object.
+        if s.fields.is_empty() {
+            self.done_list.push(format!(
+                "data {0} = {0} {{}}\n  deriving (Show, Eq, Generic)\n\ninstance FromJSON {0} where\n  parseJSON = withObject \"{0}\" (\\_ -> pure {0})\n\ninstance ToJSON {0} where\n  toJSON _ = object []\n\n",
+                s.name
+            ));
+            return Ok(());
+        }
+
+        let fields = s
+            .fields
+            .iter()
+            .map(|field| format!("{} :: {}", field.name, field.ty))
+            .collect::<Vec<_>>();
+        let keys = s
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "(\"{}\", \"{}\")",
+                    field.name,
+                    naming::escape(&field.key, &['"'])
+                )
+            })
+            .collect::<Vec<_>>();
+        self.done_list.push(format!(
+            "data {0} = {0}\n  {{ {1}\n  }}\n  deriving (Show, Eq, Generic)\n\ninstance FromJSON {0} where\n  parseJSON = genericParseJSON options{0}\n\ninstance ToJSON {0} where\n  toJSON = genericToJSON options{0}\n  toEncoding = genericToEncoding options{0}\n\noptions{0} :: Options\noptions{0} =\n  jsonOptions\n    [ {2}\n    ]\n\n",
+            s.name,
+            fields.join("\n  , "),
+            keys.join("\n    , ")
+        ));
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(&ty);
+        self.done_list.push(format!("type {} = {}\n\n", name, ty));
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &[
+            "Int",
+            "Double",
+            "Bool",
+            "Text",
+            "Maybe",
+            "Value",
+            "Options",
+            "UTCTime",
+            "LocalTime",
+            "Day",
+            "TimeOfDay",
+            "Generic",
+            "Show",
+            "Eq",
+            "FromJSON",
+            "ToJSON",
+        ]
+    }
+}
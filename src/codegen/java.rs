@@ -1,9 +1,72 @@
 use super::*;
 
+const KEYWORDS: &[&str] = &[
+    "_",
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "while",
+];
+
+struct StructItem {
+    acc: String,
+    /// Field names taken so far.
+    names: HashSet<String>,
+}
+
 #[derive(Default)]
 pub struct JavaAccumulator {
-    use_unknown: bool,
-    struct_stack: Vec<String>,
+    camel_case: bool,
+    struct_stack: Vec<StructItem>,
     done_list: Vec<String>,
 }
 
@@ -13,20 +76,60 @@ impl JavaAccumulator {
     }
 
     fn get_current(&mut self) -> &mut String {
-        self.struct_stack.last_mut().unwrap()
+        &mut self.struct_stack.last_mut().unwrap().acc
     }
 
     fn get_type(&mut self, ty: JsonType) -> String {
         match ty {
-            JsonType::Null => {
-                self.use_unknown = true;
-                String::from("Unknown")
-            }
+            JsonType::Null => String::from("Object"),
             JsonType::Number(n) => String::from(self.get_number(n)),
             JsonType::Boolean => String::from("boolean"),
             JsonType::String => String::from("String"),
+            JsonType::DateTime(DateTime::Offset) => String::from("java.time.OffsetDateTime"),
+            JsonType::DateTime(DateTime::Local) => String::from("java.time.LocalDateTime"),
+            JsonType::DateTime(DateTime::Date) => String::from("java.time.LocalDate"),
+            JsonType::DateTime(DateTime::Time) => String::from("java.time.LocalTime"),
             JsonType::Object(ty) => ty,
             JsonType::Array(ty) => format!("java.util.Vector<{}>", self.get_type(*ty)),
+            JsonType::Nullable(ty) => self.get_boxed_type(*ty),
+            JsonType::Union(_) => String::from("Object"),
+        }
+    }
+
+    /// Primitives cannot be `null`, so missing and nullable fields use their boxed counterparts.
+    fn get_boxed_type(&mut self, ty: JsonType) -> String {
+        match ty {
+            JsonType::Number(Number::Int) => String::from("Integer"),
+            JsonType::Number(Number::Float) => String::from("Float"),
+            JsonType::Boolean => String::from("Boolean"),
+            ty => self.get_type(ty),
+        }
+    }
+
+    /// Fields that differ from their keys are renamed back with Jackson.
+    fn get_field(&mut self, key: &str, ty: &str) -> String {
+        let ident = if self.camel_case {
+            naming::identifier(&naming::camel_case(key))
+        } else {
+            naming::identifier(key)
+        };
+        let names = &mut self.struct_stack.last_mut().unwrap().names;
+        let field = naming::unique(&ident, names, |ident| {
+            if KEYWORDS.contains(&ident.as_str()) {
+                ident + "_"
+            } else {
+                ident
+            }
+        });
+        if field == key {
+            format!("\tpublic {} {};\n", ty, field)
+        } else {
+            format!(
+                "\t@com.fasterxml.jackson.annotation.JsonProperty(\"{}\")\n\tpublic {} {};\n",
+                naming::escape(key, &['"']),
+                ty,
+                field
+            )
         }
     }
 
@@ -42,10 +145,6 @@ impl TypeAccumulator for JavaAccumulator {
     fn end(&mut self) -> String {
         let mut end_str = String::new();
 
-        if self.use_unknown {
-            end_str += "public class Unknown implements java.io.Serializable {}\n\n"
-        }
-
         self.done_list.iter().for_each(|done| end_str += done);
 
         end_str
@@ -53,45 +152,84 @@ impl TypeAccumulator for JavaAccumulator {
 
     fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
         let num_ty = self.get_number(number);
+        let field = self.get_field(key, num_ty);
         let acc = self.get_current();
-        *acc += &format!("\tpublic {} {};\n", num_ty, key);
+        *acc += &field;
         Ok(())
     }
 
     fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        let field = self.get_field(key, "boolean");
         let acc = self.get_current();
-        *acc += &format!("\tpublic boolean {};\n", key);
+        *acc += &field;
         Ok(())
     }
 
     fn string(&mut self, key: &str) -> Result<(), Error> {
+        let field = self.get_field(key, "String");
         let acc = self.get_current();
-        *acc += &format!("\tpublic String {};\n", key);
+        *acc += &field;
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::DateTime(date_time));
+        let field = self.get_field(key, &ty);
+        let acc = self.get_current();
+        *acc += &field;
         Ok(())
     }
 
     fn unknown(&mut self, key: &str) -> Result<(), Error> {
-        self.use_unknown = true;
+        let field = self.get_field(key, "Object");
         let acc = self.get_current();
-        *acc += &format!("\tpublic Unknown {};\n", key);
+        *acc += &field;
         Ok(())
     }
 
     fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
         let ty = self.get_type(ty);
+        let field = self.get_field(key, &format!("java.util.Vector<{}>", ty));
         let acc = self.get_current();
-        *acc += &format!("\tpublic java.util.Vector<{}> {};\n", ty, key);
+        *acc += &field;
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_boxed_type(ty);
+        let field = self.get_field(key, &ty);
+        let acc = self.get_current();
+        *acc += &field;
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, _: Vec<JsonType>) -> Result<(), Error> {
+        let field = self.get_field(key, "Object");
+        let acc = self.get_current();
+        *acc += &field;
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_boxed_type(ty);
+        let field = self.get_field(key, &ty);
+        let acc = self.get_current();
+        *acc += &field;
         Ok(())
     }
 
     fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        let field = self.get_field(key, object_name);
         let acc = self.get_current();
-        *acc += &format!("\tpublic {} {};\n", object_name, key);
+        *acc += &field;
         Ok(())
     }
 
     fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
-        self.struct_stack.push(String::new());
+        self.struct_stack.push(StructItem {
+            acc: String::new(),
+            names: HashSet::new(),
+        });
         let acc = self.get_current();
         *acc += &format!(
             "public class {} implements java.io.Serializable {{\n",
@@ -104,11 +242,50 @@ impl TypeAccumulator for JavaAccumulator {
         let acc = self.get_current();
         *acc += "}\n\n";
         let s = self.struct_stack.pop().unwrap();
-        self.done_list.push(s);
+        self.done_list.push(s.acc);
+        Ok(())
+    }
+
+    /// Java has no type aliases, so arrays are subclassed and everything else is wrapped.
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let alias = match ty {
+            JsonType::Array(_) => format!(
+                "public class {} extends {} {{}}\n\n",
+                name,
+                self.get_type(ty)
+            ),
+            ty => format!(
+                "public class {} implements java.io.Serializable {{\n\tpublic {} value;\n}}\n\n",
+                name,
+                self.get_type(ty)
+            ),
+        };
+        self.done_list.push(alias);
         Ok(())
     }
 
     fn prefered_object_name(&self) -> String {
         String::from("_Type")
     }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &[
+            "String",
+            "Integer",
+            "Float",
+            "Boolean",
+            "Object",
+            "Vector",
+            "OffsetDateTime",
+            "LocalDateTime",
+            "LocalDate",
+            "LocalTime",
+            "JsonProperty",
+            "Serializable",
+        ]
+    }
+
+    fn use_idiomatic_casing(&mut self) {
+        self.camel_case = true;
+    }
 }
@@ -0,0 +1,187 @@
+use super::*;
+use json::object;
+
+struct StructItem {
+    name: String,
+    properties: JsonValue,
+    required: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct JsonSchemaAccumulator {
+    /// See [`TypeAccumulator::set_root_name`].
+    root_name: Option<String>,
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<(String, JsonValue)>,
+}
+
+impl JsonSchemaAccumulator {
+    pub fn begin() -> Self {
+        JsonSchemaAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut StructItem {
+        self.struct_stack.last_mut().unwrap()
+    }
+
+    /// Nothing is known about [`JsonType::Null`], so anything is allowed.
+    fn get_schema(&self, ty: JsonType) -> JsonValue {
+        match ty {
+            JsonType::Null => JsonValue::new_object(),
+            JsonType::Number(Number::Int) => object! { "type": "integer" },
+            JsonType::Number(Number::Float) => object! { "type": "number" },
+            JsonType::Boolean => object! { "type": "boolean" },
+            JsonType::String => object! { "type": "string" },
+            JsonType::DateTime(DateTime::Offset) => {
+                object! { "type": "string", "format": "date-time" }
+            }
+            JsonType::DateTime(DateTime::Date) => object! { "type": "string", "format": "date" },
+            //  The `date-time` and `time` formats both need an offset.
+            JsonType::DateTime(_) => object! { "type": "string" },
+            //  The root is the document itself rather than one of its `$defs`.
+            JsonType::Object(ty) if self.root_name.as_ref() == Some(&ty) => object! { "$ref": "#" },
+            JsonType::Object(ty) => object! { "$ref": format!("#/$defs/{}", ty) },
+            JsonType::Array(ty) => object! { "type": "array", "items": self.get_schema(*ty) },
+            JsonType::Nullable(ty) => self.get_nullable_schema(*ty),
+            JsonType::Union(tys) => object! {
+                "anyOf": tys.into_iter().map(|ty| self.get_schema(ty)).collect::<Vec<_>>(),
+            },
+        }
+    }
+
+    /// Simple types add `null` to their `type`, the rest are wrapped with `anyOf`.
+    fn get_nullable_schema(&self, ty: JsonType) -> JsonValue {
+        let mut schema = self.get_schema(ty);
+        match schema["type"].as_str() {
+            Some("null") => schema,
+            None if schema.is_empty() => schema,
+            Some(ty) => {
+                schema["type"] = json::array![ty, "null"];
+                schema
+            }
+            None => object! { "anyOf": [schema, { "type": "null" }] },
+        }
+    }
+
+    fn push_property(&mut self, key: &str, schema: JsonValue, required: bool) {
+        let acc = self.get_current();
+        acc.properties[key] = schema;
+        if required {
+            acc.required.push(String::from(key));
+        }
+    }
+}
+
+impl TypeAccumulator for JsonSchemaAccumulator {
+    /// The root type becomes the document itself and every other type goes into `$defs`.
+    fn end(&mut self) -> String {
+        let mut document = object! { "$schema": "https://json-schema.org/draft/2020-12/schema" };
+
+        let root = self
+            .done_list
+            .iter()
+            .position(|(name, _)| Some(name) == self.root_name.as_ref());
+        if let Some(root) = root {
+            let (name, schema) = self.done_list.remove(root);
+            document["title"] = name.into();
+            schema.entries().for_each(|(key, value)| {
+                document[key] = value.clone();
+            });
+        }
+
+        if !self.done_list.is_empty() {
+            let mut defs = JsonValue::new_object();
+            self.done_list.iter().for_each(|(name, schema)| {
+                defs[name.as_str()] = schema.clone();
+            });
+            document["$defs"] = defs;
+        }
+
+        document.pretty(2)
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        self.push_property(key, self.get_schema(JsonType::Number(number)), true);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_property(key, self.get_schema(JsonType::Boolean), true);
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        self.push_property(key, self.get_schema(JsonType::String), true);
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        self.push_property(key, self.get_schema(JsonType::DateTime(date_time)), true);
+        Ok(())
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        self.push_property(key, self.get_schema(JsonType::Null), true);
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let schema = self.get_schema(JsonType::Array(Box::new(ty)));
+        self.push_property(key, schema, true);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_property(key, self.get_nullable_schema(ty), true);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        self.push_property(key, self.get_schema(JsonType::Union(tys)), true);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_property(key, self.get_schema(ty), false);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        let schema = self.get_schema(JsonType::Object(String::from(object_name)));
+        self.push_property(key, schema, true);
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.struct_stack.push(StructItem {
+            name: String::from(object_name),
+            properties: JsonValue::new_object(),
+            required: vec![],
+        });
+        Ok(())
+    }
+
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        let s = self.struct_stack.pop().unwrap();
+        let mut schema = object! { "type": "object", "properties": s.properties };
+        if !s.required.is_empty() {
+            schema["required"] = s.required.into();
+        }
+        self.done_list.push((s.name, schema));
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let schema = self.get_schema(ty);
+        self.done_list.push((String::from(name), schema));
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+
+    fn set_root_name(&mut self, name: &str) {
+        self.root_name = Some(String::from(name));
+    }
+}
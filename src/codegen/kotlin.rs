@@ -1,9 +1,50 @@
 use super::*;
 
+const KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+struct StructItem {
+    acc: String,
+    /// Field names taken so far.
+    names: HashSet<String>,
+}
+
 #[derive(Default)]
 pub struct KotlinAccumulator {
-    use_unknown: bool,
-    struct_stack: Vec<String>,
+    camel_case: bool,
+    use_json_element: bool,
+    /// Types used from `kotlinx.datetime`.
+    datetime_list: Vec<&'static str>,
+    union_list: Vec<String>,
+    struct_stack: Vec<StructItem>,
     done_list: Vec<String>,
 }
 
@@ -13,20 +54,119 @@ impl KotlinAccumulator {
     }
 
     fn get_current(&mut self) -> &mut String {
-        self.struct_stack.last_mut().unwrap()
+        &mut self.struct_stack.last_mut().unwrap().acc
     }
 
     fn get_type(&mut self, ty: JsonType) -> String {
         match ty {
             JsonType::Null => {
-                self.use_unknown = true;
-                String::from("Unknown")
+                self.use_json_element = true;
+                String::from("JsonElement?")
             }
             JsonType::Number(n) => String::from(self.get_number(n)),
             JsonType::Boolean => String::from("Boolean"),
             JsonType::String => String::from("String"),
+            JsonType::DateTime(date_time) => {
+                let ty = match date_time {
+                    DateTime::Offset => "Instant",
+                    DateTime::Local => "LocalDateTime",
+                    DateTime::Date => "LocalDate",
+                    DateTime::Time => "LocalTime",
+                };
+                if !self.datetime_list.contains(&ty) {
+                    self.datetime_list.push(ty);
+                }
+                String::from(ty)
+            }
             JsonType::Object(ty) => ty,
             JsonType::Array(ty) => format!("Array<{}>", self.get_type(*ty)),
+            JsonType::Nullable(ty) => format!("{}?", self.get_type(*ty)),
+            JsonType::Union(tys) => self.get_union(tys),
+        }
+    }
+
+    /// Declares a sealed class and its serializer the first time the union is seen.
+    /// Deserializing tries each subclass in order.
+    fn get_union(&mut self, tys: Vec<JsonType>) -> String {
+        let name = union_name(&tys);
+        if !self.union_list.contains(&name) {
+            self.union_list.push(name.clone());
+            let cases = tys
+                .into_iter()
+                .map(|ty| (union_variant_name(&ty) + "Value", self.get_type(ty)))
+                .collect::<Vec<_>>();
+
+            let mut acc = format!(
+                "@Serializable(with = {0}Serializer::class)\nsealed class {0} {{\n",
+                name
+            );
+            cases.iter().for_each(|(case, ty)| {
+                acc += &format!("\tdata class {}(val value: {}) : {}()\n", case, ty, name);
+            });
+            acc += "}\n\n";
+            acc += &format!("object {0}Serializer : KSerializer<{0}> {{\n", name);
+            acc += &format!(
+                "\toverride val descriptor: SerialDescriptor = buildClassSerialDescriptor(\"{}\")\n\n",
+                name
+            );
+            acc += &format!(
+                "\toverride fun deserialize(decoder: Decoder): {} {{\n",
+                name
+            );
+            acc += "\t\tval input = decoder as JsonDecoder\n";
+            acc += "\t\tval element = input.decodeJsonElement()\n";
+            cases.iter().for_each(|(case, ty)| {
+                acc += &format!(
+                    "\t\trunCatching {{ return {}.{}(input.json.decodeFromJsonElement<{}>(element)) }}\n",
+                    name, case, ty
+                );
+            });
+            acc += &format!(
+                "\t\tthrow SerializationException(\"Unexpected type for {}\")\n\t}}\n\n",
+                name
+            );
+            acc += &format!(
+                "\toverride fun serialize(encoder: Encoder, value: {}) {{\n",
+                name
+            );
+            acc += "\t\tval output = encoder as JsonEncoder\n";
+            acc += "\t\tval element = when (value) {\n";
+            cases.iter().for_each(|(case, _)| {
+                acc += &format!(
+                    "\t\t\tis {}.{} -> output.json.encodeToJsonElement(value.value)\n",
+                    name, case
+                );
+            });
+            acc += "\t\t}\n\t\toutput.encodeJsonElement(element)\n\t}\n}\n\n";
+            self.done_list.push(acc);
+        }
+        name
+    }
+
+    /// Keywords are escaped with backticks.
+    /// Fields that differ from their keys are renamed back with `@SerialName`.
+    fn get_val(&mut self, key: &str) -> String {
+        let ident = if self.camel_case {
+            naming::identifier(&naming::camel_case(key))
+        } else {
+            naming::identifier(key)
+        };
+        let names = &mut self.struct_stack.last_mut().unwrap().names;
+        let name = naming::unique(&ident, names, |ident| {
+            if KEYWORDS.contains(&ident.as_str()) {
+                format!("`{}`", ident)
+            } else {
+                ident
+            }
+        });
+        if name.trim_matches('`') == key {
+            format!("val {}", name)
+        } else {
+            format!(
+                "@SerialName(\"{}\") val {}",
+                naming::escape(key, &['"', '$']),
+                name
+            )
         }
     }
 
@@ -42,16 +182,33 @@ impl TypeAccumulator for KotlinAccumulator {
     fn end(&mut self) -> String {
         let mut end_str = String::new();
 
-        if self.use_unknown {
-            end_str += "class Unknown;\n\n"
-        }
-
         self.done_list.iter().for_each(|done| end_str += done);
 
-        end_str += r#"//  import kotlinx.serialization.Serializable
+        end_str += r#"//  import kotlinx.serialization.SerialName
+//  import kotlinx.serialization.Serializable
 //  import kotlinx.serialization.json.Json
 //  import kotlinx.serialization.encodeToString
-//
+"#;
+
+        if self.use_json_element {
+            end_str += "//  import kotlinx.serialization.json.JsonElement\n";
+        }
+
+        self.datetime_list.sort();
+        self.datetime_list
+            .iter()
+            .for_each(|ty| end_str += &format!("//  import kotlinx.datetime.{}\n", ty));
+
+        if !self.union_list.is_empty() {
+            end_str += r#"//  import kotlinx.serialization.KSerializer
+//  import kotlinx.serialization.SerializationException
+//  import kotlinx.serialization.descriptors.*
+//  import kotlinx.serialization.encoding.*
+//  import kotlinx.serialization.json.*
+"#;
+        }
+
+        end_str += r#"//
 //  val json = Json.encodeToString(T)
 //  val ty = Json.decodeFromString<T>(json)"#;
 
@@ -60,45 +217,89 @@ impl TypeAccumulator for KotlinAccumulator {
 
     fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
         let num_ty = self.get_number(number);
+        let val = self.get_val(key);
         let acc = self.get_current();
-        *acc += &format!("\tval {}: {},\n", key, num_ty);
+        *acc += &format!("\t{}: {},\n", val, num_ty);
         Ok(())
     }
 
     fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        let val = self.get_val(key);
         let acc = self.get_current();
-        *acc += &format!("\tval {}: bool,\n", key);
+        *acc += &format!("\t{}: bool,\n", val);
         Ok(())
     }
 
     fn string(&mut self, key: &str) -> Result<(), Error> {
+        let val = self.get_val(key);
         let acc = self.get_current();
-        *acc += &format!("\tval {}: String,\n", key);
+        *acc += &format!("\t{}: String,\n", val);
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::DateTime(date_time));
+        let val = self.get_val(key);
+        let acc = self.get_current();
+        *acc += &format!("\t{}: {},\n", val, ty);
         Ok(())
     }
 
     fn unknown(&mut self, key: &str) -> Result<(), Error> {
-        self.use_unknown = true;
+        self.use_json_element = true;
+        let val = self.get_val(key);
         let acc = self.get_current();
-        *acc += &format!("\tval {}: Unknown,\n", key);
+        *acc += &format!("\t{}: JsonElement?,\n", val);
         Ok(())
     }
 
     fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
         let ty = self.get_type(ty);
+        let val = self.get_val(key);
+        let acc = self.get_current();
+        *acc += &format!("\t{}: Array<{}>,\n", val, ty);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        let val = self.get_val(key);
+        let acc = self.get_current();
+        *acc += &format!("\t{}: {}?,\n", val, ty);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        let ty = self.get_union(tys);
+        let val = self.get_val(key);
+        let acc = self.get_current();
+        *acc += &format!("\t{}: {},\n", val, ty);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = match ty {
+            JsonType::Null | JsonType::Nullable(_) => self.get_type(ty),
+            ty => format!("{}?", self.get_type(ty)),
+        };
+        let val = self.get_val(key);
         let acc = self.get_current();
-        *acc += &format!("\tval {}: Array<{}>,\n", key, ty);
+        *acc += &format!("\t{}: {} = null,\n", val, ty);
         Ok(())
     }
 
     fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        let val = self.get_val(key);
         let acc = self.get_current();
-        *acc += &format!("\tval {}: {},\n", key, object_name);
+        *acc += &format!("\t{}: {},\n", val, object_name);
         Ok(())
     }
 
     fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
-        self.struct_stack.push(String::new());
+        self.struct_stack.push(StructItem {
+            acc: String::new(),
+            names: HashSet::new(),
+        });
         let acc = self.get_current();
         *acc += &format!(
             "@Serializable\nclass {} {{\n",
@@ -111,11 +312,47 @@ impl TypeAccumulator for KotlinAccumulator {
         let acc = self.get_current();
         *acc += "}\n\n";
         let s = self.struct_stack.pop().unwrap();
-        self.done_list.push(s);
+        self.done_list.push(s.acc);
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.done_list
+            .push(format!("typealias {} = {}\n\n", name, ty));
         Ok(())
     }
 
     fn prefered_object_name(&self) -> String {
         String::from("_Type")
     }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &[
+            "String",
+            "Int",
+            "Double",
+            "Boolean",
+            "Array",
+            "Instant",
+            "LocalDateTime",
+            "LocalDate",
+            "LocalTime",
+            "Json",
+            "JsonElement",
+            "Serializable",
+            "SerialName",
+            "KSerializer",
+            "SerializationException",
+            "SerialDescriptor",
+            "Decoder",
+            "Encoder",
+            "JsonDecoder",
+            "JsonEncoder",
+        ]
+    }
+
+    fn use_idiomatic_casing(&mut self) {
+        self.camel_case = true;
+    }
 }
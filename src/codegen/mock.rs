@@ -28,6 +28,11 @@ impl TypeAccumulator for MockAccumulator {
         Ok(())
     }
 
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        self.0 += &format!("date:{}:{:?}\n", key, date_time);
+        Ok(())
+    }
+
     fn unknown(&mut self, key: &str) -> Result<(), Error> {
         self.0 += &format!("null:{}\n", key);
         Ok(())
@@ -38,6 +43,21 @@ impl TypeAccumulator for MockAccumulator {
         Ok(())
     }
 
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.0 += &format!("nullable:{}:{:?}\n", key, ty);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        self.0 += &format!("union:{}:{:?}\n", key, tys);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.0 += &format!("opt:{}:{:?}\n", key, ty);
+        Ok(())
+    }
+
     fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
         self.0 += &format!("obj:{}:{}\n", key, object_name);
         Ok(())
@@ -53,6 +73,11 @@ impl TypeAccumulator for MockAccumulator {
         Ok(())
     }
 
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        self.0 += &format!("alias:{}:{:?}\n", name, ty);
+        Ok(())
+    }
+
     fn prefered_object_name(&self) -> String {
         String::from("_")
     }
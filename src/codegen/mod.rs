@@ -5,33 +5,63 @@
 
 use super::*;
 
+mod cpp;
+mod csharp;
 mod dart;
+mod elm;
+mod go;
+mod haskell;
 mod java;
+mod json_schema;
 mod kotlin;
 mod mock;
+mod pydantic;
 mod python;
+mod python_dataclass;
 mod rust;
+mod scala;
 mod swift;
 mod typescript;
+mod zod;
 
+/// C++ support using `nlohmann::json`.
+pub use cpp::CppAccumulator;
+/// C# support using `System.Text.Json`.
+pub use csharp::CSharpAccumulator;
 /// Dart support.
 pub use dart::DartAccumulator;
+/// Elm support with `Json.Decode` decoders and `Json.Encode` encoders.
+pub use elm::ElmAccumulator;
+/// Go support using `encoding/json`.
+pub use go::GoAccumulator;
+/// Haskell support using Aeson.
+pub use haskell::HaskellAccumulator;
 /// Java Serializable support.
 pub use java::JavaAccumulator;
+/// JSON Schema draft 2020-12 support.
+pub use json_schema::JsonSchemaAccumulator;
 /// Kotlin support.
 pub use kotlin::KotlinAccumulator;
 /// Testing purposes only.
 pub use mock::MockAccumulator;
+/// Pydantic v2 support.
+pub use pydantic::PydanticAccumulator;
 /// Python 3.8+ support.
 pub use python::PythonAccumulator;
+/// Python dataclass support without third-party packages.
+pub use python_dataclass::PythonDataclassAccumulator;
 /// Rust support using serde.
 pub use rust::RustAccumulator;
+/// Scala support using circe.
+pub use scala::ScalaAccumulator;
 /// Swift support.
 pub use swift::SwiftAccumulator;
 /// Typescript support.
 pub use typescript::TypescriptAccumulator;
+/// Typescript support with runtime validation using Zod.
+pub use zod::ZodAccumulator;
 
-/// The list of supported accumulators: `["typescript", "python", "dart", "rust", "java", "kotlin", "mock"]`.
+/// The list of supported accumulators: `["typescript", "python", "dart", "rust", "java", "kotlin", "swift", "go", "csharp", "cpp", "zod", "jsonschema", "pydantic", "dataclass", "elm", "haskell", "scala", "mock"]`.
 pub const ACCUMULATOR_SUPPORT_LIST: &[&str] = &[
     "typescript",
     "python",
@@ -40,9 +70,46 @@ pub const ACCUMULATOR_SUPPORT_LIST: &[&str] = &[
     "java",
     "kotlin",
     "swift",
+    "go",
+    "csharp",
+    "cpp",
+    "zod",
+    "jsonschema",
+    "pydantic",
+    "dataclass",
+    "elm",
+    "haskell",
+    "scala",
     "mock",
 ];
 
+/// A name for a variant of [`JsonType::Union`], for languages that name each one.
+fn union_variant_name(ty: &JsonType) -> String {
+    match ty {
+        JsonType::Null => String::from("Null"),
+        JsonType::Number(Number::Int) => String::from("Int"),
+        JsonType::Number(Number::Float) => String::from("Float"),
+        JsonType::Boolean => String::from("Bool"),
+        JsonType::String => String::from("String"),
+        JsonType::DateTime(DateTime::Offset) => String::from("DateTime"),
+        JsonType::DateTime(DateTime::Local) => String::from("LocalDateTime"),
+        JsonType::DateTime(DateTime::Date) => String::from("Date"),
+        JsonType::DateTime(DateTime::Time) => String::from("Time"),
+        JsonType::Object(name) => name.clone(),
+        JsonType::Array(ty) => union_variant_name(ty) + "List",
+        JsonType::Nullable(ty) => String::from("Nullable") + &union_variant_name(ty),
+        JsonType::Union(tys) => union_name(tys),
+    }
+}
+
+/// A name for [`JsonType::Union`], for languages that must declare it as its own type.
+fn union_name(tys: &[JsonType]) -> String {
+    tys.iter()
+        .map(union_variant_name)
+        .collect::<Vec<_>>()
+        .join("Or")
+}
+
 /// Choose an accumulator from [`ACCUMULATOR_SUPPORT_LIST`]
 pub fn accumulator_choose_with_str(s: &str) -> Option<Box<dyn TypeAccumulator>> {
     Some(match s {
@@ -53,6 +120,16 @@ pub fn accumulator_choose_with_str(s: &str) -> Option<Box<dyn TypeAccumulator>>
         "kotlin" => Box::new(KotlinAccumulator::begin()),
         "python" => Box::new(PythonAccumulator::begin()),
         "swift" => Box::new(SwiftAccumulator::begin()),
+        "go" => Box::new(GoAccumulator::begin()),
+        "csharp" => Box::new(CSharpAccumulator::begin()),
+        "cpp" => Box::new(CppAccumulator::begin()),
+        "zod" => Box::new(ZodAccumulator::begin()),
+        "jsonschema" => Box::new(JsonSchemaAccumulator::begin()),
+        "pydantic" => Box::new(PydanticAccumulator::begin()),
+        "dataclass" => Box::new(PythonDataclassAccumulator::begin()),
+        "elm" => Box::new(ElmAccumulator::begin()),
+        "haskell" => Box::new(HaskellAccumulator::begin()),
+        "scala" => Box::new(ScalaAccumulator::begin()),
         "mock" => Box::new(MockAccumulator::begin()),
         _ => None?,
     })
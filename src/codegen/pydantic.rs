@@ -0,0 +1,327 @@
+use super::*;
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Attributes of `BaseModel` that fields would shadow.
+/// Anything starting with `model_` is also left to Pydantic.
+const MODEL_ATTRIBUTES: &[&str] = &[
+    "construct",
+    "copy",
+    "dict",
+    "fields",
+    "from_orm",
+    "json",
+    "parse_file",
+    "parse_obj",
+    "parse_raw",
+    "schema",
+    "schema_json",
+    "update_forward_refs",
+    "validate",
+];
+
+struct StructItem {
+    name: String,
+    acc: String,
+    /// Refers to a model that is not defined yet, see [`PydanticAccumulator::get_object`].
+    forward_ref: bool,
+    is_empty: bool,
+    /// Field names taken so far.
+    names: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct PydanticAccumulator {
+    use_any: bool,
+    use_optional: bool,
+    use_union: bool,
+    use_field: bool,
+    use_root_model: bool,
+    use_aware_datetime: bool,
+    use_naive_datetime: bool,
+    /// Names used from the `datetime` module.
+    datetime_list: Vec<&'static str>,
+    /// Models to rebuild once every model is defined.
+    rebuild_list: Vec<String>,
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<String>,
+}
+
+impl PydanticAccumulator {
+    pub fn begin() -> Self {
+        PydanticAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut StructItem {
+        self.struct_stack.last_mut().unwrap()
+    }
+
+    fn get_type(&mut self, ty: JsonType) -> String {
+        match ty {
+            JsonType::Null => {
+                self.use_any = true;
+                String::from("Any")
+            }
+            JsonType::Number(n) => String::from(self.get_number(n)),
+            JsonType::Boolean => String::from("bool"),
+            JsonType::String => String::from("str"),
+            //  Pydantic's own types check that the offset is there, or not.
+            JsonType::DateTime(DateTime::Offset) => {
+                self.use_aware_datetime = true;
+                String::from("AwareDatetime")
+            }
+            JsonType::DateTime(DateTime::Local) => {
+                self.use_naive_datetime = true;
+                String::from("NaiveDatetime")
+            }
+            JsonType::DateTime(date_time) => {
+                let ty = match date_time {
+                    DateTime::Date => "date",
+                    _ => "time",
+                };
+                if !self.datetime_list.contains(&ty) {
+                    self.datetime_list.push(ty);
+                }
+                String::from(ty)
+            }
+            JsonType::Object(ty) => self.get_object(ty),
+            JsonType::Array(ty) => format!("list[{}]", self.get_type(*ty)),
+            JsonType::Nullable(ty) => {
+                self.use_optional = true;
+                format!("Optional[{}]", self.get_type(*ty))
+            }
+            JsonType::Union(tys) => {
+                self.use_union = true;
+                let tys = tys
+                    .into_iter()
+                    .map(|ty| self.get_type(ty))
+                    .collect::<Vec<_>>();
+                format!("Union[{}]", tys.join(", "))
+            }
+        }
+    }
+
+    /// Both `null` and missing keys default to `None`.
+    fn get_optional_type(&mut self, ty: JsonType) -> String {
+        match ty {
+            JsonType::Null | JsonType::Nullable(_) => self.get_type(ty),
+            ty => self.get_type(JsonType::Nullable(Box::new(ty))),
+        }
+    }
+
+    /// Keys that are not valid field names get an alias.
+    /// Leading underscores are dropped since pydantic treats those as private attributes.
+    fn push_field(&mut self, key: &str, ty: &str, default_none: bool) {
+        let mut field = naming::identifier(key).trim_start_matches('_').to_owned();
+        if !field.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            field = String::from("field_") + &field;
+        }
+        let names = &mut self.get_current().names;
+        let field = naming::unique(&field, names, |field| {
+            if field.starts_with("model_") {
+                String::from("field_") + &field
+            } else if KEYWORDS.contains(&field.as_str())
+                || MODEL_ATTRIBUTES.contains(&field.as_str())
+            {
+                field + "_"
+            } else {
+                field
+            }
+        });
+
+        let value = match (field == key, default_none) {
+            (true, true) => String::from(" = None"),
+            (true, false) => String::new(),
+            (false, true) => format!(
+                " = Field(default=None, alias=\"{}\")",
+                naming::escape(key, &['"'])
+            ),
+            (false, false) => format!(" = Field(alias=\"{}\")", naming::escape(key, &['"'])),
+        };
+        if field != key {
+            self.use_field = true;
+        }
+
+        let acc = self.get_current();
+        acc.acc += &format!("\t{}: {}{}\n", field, ty, value);
+        acc.is_empty = false;
+    }
+
+    /// Models that are still being declared are not defined yet, so they are quoted
+    /// and the model referring to them is rebuilt at the end.
+    fn get_object(&mut self, name: String) -> String {
+        if self.struct_stack.iter().any(|s| s.name == name) {
+            self.get_current().forward_ref = true;
+            format!("\"{}\"", name)
+        } else {
+            name
+        }
+    }
+
+    fn get_number(&self, num: Number) -> &'static str {
+        match num {
+            Number::Int => "int",
+            Number::Float => "float",
+        }
+    }
+}
+
+impl TypeAccumulator for PydanticAccumulator {
+    fn end(&mut self) -> String {
+        let mut end_str = String::new();
+
+        if !self.datetime_list.is_empty() {
+            self.datetime_list.sort();
+            end_str += &format!("from datetime import {}\n", self.datetime_list.join(", "));
+        }
+        let typing = [
+            (self.use_any, "Any"),
+            (self.use_optional, "Optional"),
+            (self.use_union, "Union"),
+        ]
+        .iter()
+        .filter(|(used, _)| *used)
+        .map(|(_, import)| *import)
+        .collect::<Vec<_>>();
+        if !typing.is_empty() {
+            end_str += &format!("from typing import {}\n", typing.join(", "));
+        }
+
+        let mut pydantic = String::new();
+        if self.use_aware_datetime {
+            pydantic += "AwareDatetime, ";
+        }
+        pydantic += "BaseModel";
+        if self.use_field {
+            pydantic += ", Field";
+        }
+        if self.use_naive_datetime {
+            pydantic += ", NaiveDatetime";
+        }
+        if self.use_root_model {
+            pydantic += ", RootModel";
+        }
+        end_str += &format!("from pydantic import {}\n\n\n", pydantic);
+
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        self.rebuild_list
+            .iter()
+            .for_each(|name| end_str += &format!("{}.model_rebuild()\n", name));
+
+        end_str
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        let num_ty = self.get_number(number);
+        self.push_field(key, num_ty, false);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, "bool", false);
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, "str", false);
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::DateTime(date_time));
+        self.push_field(key, &ty, false);
+        Ok(())
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        self.use_any = true;
+        self.push_field(key, "Any", true);
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.push_field(key, &format!("list[{}]", ty), false);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_optional_type(ty);
+        self.push_field(key, &ty, true);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::Union(tys));
+        self.push_field(key, &ty, false);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_optional_type(ty);
+        self.push_field(key, &ty, true);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        let ty = self.get_object(String::from(object_name));
+        self.push_field(key, &ty, false);
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.struct_stack.push(StructItem {
+            name: String::from(object_name),
+            acc: format!("class {}(BaseModel):\n", object_name),
+            forward_ref: false,
+            is_empty: true,
+            names: HashSet::new(),
+        });
+        Ok(())
+    }
+
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        let acc = self.get_current();
+        if acc.is_empty {
+            acc.acc += "\tpass\n";
+        }
+        acc.acc += "\n\n";
+        let s = self.struct_stack.pop().unwrap();
+        if s.forward_ref {
+            self.rebuild_list.push(s.name);
+        }
+        self.done_list.push(s.acc);
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        self.use_root_model = true;
+        let ty = self.get_type(ty);
+        self.done_list
+            .push(format!("class {}(RootModel[{}]):\n\tpass\n\n\n", name, ty));
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &[
+            "BaseModel",
+            "RootModel",
+            "AwareDatetime",
+            "NaiveDatetime",
+            "Field",
+            "Any",
+            "Optional",
+            "Union",
+        ]
+    }
+}
@@ -1,9 +1,26 @@
 use super::*;
 
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+struct StructItem {
+    name: String,
+    fields: Vec<(String, String)>,
+}
+
 #[derive(Default)]
 pub struct PythonAccumulator {
-    use_unknown: bool,
-    struct_stack: Vec<String>,
+    use_any: bool,
+    use_optional: bool,
+    use_union: bool,
+    use_not_required: bool,
+    /// Names used from the `datetime` module.
+    datetime_list: Vec<&'static str>,
+    struct_stack: Vec<StructItem>,
     done_list: Vec<String>,
 }
 
@@ -12,21 +29,43 @@ impl PythonAccumulator {
         PythonAccumulator::default()
     }
 
-    fn get_current(&mut self) -> &mut String {
+    fn get_current(&mut self) -> &mut StructItem {
         self.struct_stack.last_mut().unwrap()
     }
 
     fn get_type(&mut self, ty: JsonType) -> String {
         match ty {
             JsonType::Null => {
-                self.use_unknown = true;
-                String::from("Unknown")
+                self.use_any = true;
+                String::from("Any")
             }
             JsonType::Number(n) => String::from(self.get_number(n)),
             JsonType::Boolean => String::from("bool"),
             JsonType::String => String::from("str"),
-            JsonType::Object(ty) => ty,
+            JsonType::DateTime(date_time) => String::from(self.get_datetime(date_time)),
+            JsonType::Object(ty) => self.get_object(ty),
             JsonType::Array(ty) => format!("list[{}]", self.get_type(*ty)),
+            JsonType::Nullable(ty) => {
+                self.use_optional = true;
+                format!("Optional[{}]", self.get_type(*ty))
+            }
+            JsonType::Union(tys) => {
+                self.use_union = true;
+                let tys = tys
+                    .into_iter()
+                    .map(|ty| self.get_type(ty))
+                    .collect::<Vec<_>>();
+                format!("Union[{}]", tys.join(", "))
+            }
+        }
+    }
+
+    /// Classes that are still being declared are not defined yet, so they are quoted.
+    fn get_object(&self, name: String) -> String {
+        if self.struct_stack.iter().any(|s| s.name == name) {
+            format!("\"{}\"", name)
+        } else {
+            name
         }
     }
 
@@ -36,21 +75,61 @@ impl PythonAccumulator {
             Number::Float => "float",
         }
     }
+
+    /// `tomllib` reads local dates and times without an offset as naive `datetime`s.
+    fn get_datetime(&mut self, date_time: DateTime) -> &'static str {
+        let ty = match date_time {
+            DateTime::Offset | DateTime::Local => "datetime",
+            DateTime::Date => "date",
+            DateTime::Time => "time",
+        };
+        if !self.datetime_list.contains(&ty) {
+            self.datetime_list.push(ty);
+        }
+        ty
+    }
 }
 
 impl TypeAccumulator for PythonAccumulator {
     fn end(&mut self) -> String {
         let mut end_str = String::new();
 
-        if self.use_unknown {
-            end_str += "class Unknown(TypedDict):\n\tpass\n\n"
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        let mut imports = String::from("TypedDict");
+        if self.use_any {
+            imports += ", Any";
+        }
+        if self.use_optional {
+            imports += ", Optional";
+        }
+        if self.use_union {
+            imports += ", Union";
         }
 
-        self.done_list.iter().for_each(|done| end_str += done);
+        end_str += &format!(
+            r#"# This accumulator relies on `TypedDict`.
+# Import with `from typing import {}`.
+# Note that this is only supported on Python 3.8+"#,
+            imports
+        );
+
+        if self.use_not_required {
+            end_str += r#"
+# Optional keys rely on `NotRequired`.
+# Import with `from typing import NotRequired` on Python 3.11+,
+# or with `from typing_extensions import NotRequired` on older versions."#;
+        }
 
-        end_str += r#"# This accumulator relies on `TypedDict`.
-# Import with `from typing import TypedDict`.
-# Note that this is only supported on Python 3.8+"#;
+        if !self.datetime_list.is_empty() {
+            self.datetime_list.sort();
+            end_str += &format!(
+                r#"
+# Dates and times rely on `datetime`.
+# Import with `from datetime import {}`."#,
+                self.datetime_list.join(", ")
+            );
+        }
 
         end_str
     }
@@ -58,58 +137,119 @@ impl TypeAccumulator for PythonAccumulator {
     fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
         let num_ty = self.get_number(number);
         let acc = self.get_current();
-        *acc += &format!("\t{}: {}\n", key, num_ty);
+        acc.fields.push((String::from(key), String::from(num_ty)));
         Ok(())
     }
 
     fn boolean(&mut self, key: &str) -> Result<(), Error> {
         let acc = self.get_current();
-        *acc += &format!("\t{}: bool\n", key);
+        acc.fields.push((String::from(key), String::from("bool")));
         Ok(())
     }
 
     fn string(&mut self, key: &str) -> Result<(), Error> {
         let acc = self.get_current();
-        *acc += &format!("\t{}: str\n", key);
+        acc.fields.push((String::from(key), String::from("str")));
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::DateTime(date_time));
+        let acc = self.get_current();
+        acc.fields.push((String::from(key), ty));
         Ok(())
     }
 
     fn unknown(&mut self, key: &str) -> Result<(), Error> {
-        self.use_unknown = true;
+        self.use_any = true;
         let acc = self.get_current();
-        *acc += &format!("\t{}: Unknown\n", key);
+        acc.fields.push((String::from(key), String::from("Any")));
         Ok(())
     }
 
     fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
         let ty = self.get_type(ty);
         let acc = self.get_current();
-        *acc += &format!("\t{}: list[{}]\n", key, ty);
+        acc.fields
+            .push((String::from(key), format!("list[{}]", ty)));
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.use_optional = true;
+        let ty = self.get_type(ty);
+        let acc = self.get_current();
+        acc.fields
+            .push((String::from(key), format!("Optional[{}]", ty)));
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::Union(tys));
+        let acc = self.get_current();
+        acc.fields.push((String::from(key), ty));
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.use_not_required = true;
+        let ty = self.get_type(ty);
+        let acc = self.get_current();
+        acc.fields
+            .push((String::from(key), format!("NotRequired[{}]", ty)));
         Ok(())
     }
 
     fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        let ty = self.get_object(String::from(object_name));
         let acc = self.get_current();
-        *acc += &format!("\t{}: {}\n", key, object_name);
+        acc.fields.push((String::from(key), ty));
         Ok(())
     }
 
     fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
-        self.struct_stack.push(String::new());
-        let acc = self.get_current();
-        *acc += &format!("class {}(TypedDict):\n", object_name);
+        self.struct_stack.push(StructItem {
+            name: String::from(object_name),
+            fields: vec![],
+        });
         Ok(())
     }
 
     fn pop_object_type(&mut self) -> Result<(), Error> {
-        let acc = self.get_current();
-        *acc += "\n\n";
         let s = self.struct_stack.pop().unwrap();
-        self.done_list.push(s);
+        let is_class_syntax = s
+            .fields
+            .iter()
+            .all(|(key, _)| naming::is_identifier(key) && !KEYWORDS.contains(&key.as_str()));
+        let acc = if is_class_syntax {
+            let mut acc = format!("class {}(TypedDict):\n", s.name);
+            s.fields.iter().for_each(|(key, ty)| {
+                acc += &format!("\t{}: {}\n", key, ty);
+            });
+            acc
+        } else {
+            //  Keys that are not identifiers only fit in the functional syntax.
+            let mut acc = format!("{0} = TypedDict(\"{0}\", {{\n", s.name);
+            s.fields.iter().for_each(|(key, ty)| {
+                acc += &format!("\t\"{}\": {},\n", naming::escape(key, &['"']), ty);
+            });
+            acc + "})\n"
+        };
+        self.done_list.push(acc + "\n\n");
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.done_list.push(format!("{} = {}\n\n\n", name, ty));
         Ok(())
     }
 
     fn prefered_object_name(&self) -> String {
         String::from("_Type")
     }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &["TypedDict", "NotRequired", "Any", "Optional", "Union"]
+    }
 }
@@ -0,0 +1,349 @@
+use super::*;
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "self", "try",
+    "while", "with", "yield",
+];
+
+struct Field {
+    name: String,
+    key: String,
+    /// The annotation, such as `Optional[list[Item]]`.
+    annotation: String,
+    ty: JsonType,
+    /// Missing from some objects, so it defaults to `None`.
+    optional: bool,
+}
+
+struct StructItem {
+    name: String,
+    fields: Vec<Field>,
+    /// Field names taken so far, starting with the methods every dataclass has.
+    names: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct PythonDataclassAccumulator {
+    use_optional: bool,
+    use_union: bool,
+    /// Names used from the `datetime` module.
+    datetime_list: Vec<&'static str>,
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<String>,
+}
+
+impl PythonDataclassAccumulator {
+    pub fn begin() -> Self {
+        PythonDataclassAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut StructItem {
+        self.struct_stack.last_mut().unwrap()
+    }
+
+    fn get_type(&mut self, ty: &JsonType) -> String {
+        match ty {
+            JsonType::Null => String::from("Any"),
+            JsonType::Number(n) => String::from(self.get_number(*n)),
+            JsonType::Boolean => String::from("bool"),
+            JsonType::String => String::from("str"),
+            JsonType::DateTime(date_time) => {
+                let ty = Self::get_datetime(*date_time);
+                if !self.datetime_list.contains(&ty) {
+                    self.datetime_list.push(ty);
+                }
+                String::from(ty)
+            }
+            //  Classes that are still being declared are not defined yet, so they are quoted.
+            JsonType::Object(ty) if self.struct_stack.iter().any(|s| &s.name == ty) => {
+                format!("\"{}\"", ty)
+            }
+            JsonType::Object(ty) => ty.clone(),
+            JsonType::Array(ty) => format!("list[{}]", self.get_type(ty)),
+            JsonType::Nullable(ty) => {
+                self.use_optional = true;
+                format!("Optional[{}]", self.get_type(ty))
+            }
+            JsonType::Union(tys) => {
+                self.use_union = true;
+                let tys = tys.iter().map(|ty| self.get_type(ty)).collect::<Vec<_>>();
+                format!("Union[{}]", tys.join(", "))
+            }
+        }
+    }
+
+    /// Local dates and times without an offset are naive `datetime`s.
+    fn get_datetime(date_time: DateTime) -> &'static str {
+        match date_time {
+            DateTime::Offset | DateTime::Local => "datetime",
+            DateTime::Date => "date",
+            DateTime::Time => "time",
+        }
+    }
+
+    /// Only objects, dates and times, and whatever holds them, need converting.
+    /// Unions are left alone since there is no telling which variant a value is.
+    fn needs_conversion(ty: &JsonType) -> bool {
+        match ty {
+            JsonType::Object(_) | JsonType::DateTime(_) => true,
+            JsonType::Array(ty) | JsonType::Nullable(ty) => Self::needs_conversion(ty),
+            _ => false,
+        }
+    }
+
+    /// `value` is the expression holding the json value.
+    /// `depth` names the comprehension variables of nested lists.
+    fn from_dict(value: &str, ty: &JsonType, depth: usize) -> String {
+        match ty {
+            _ if !Self::needs_conversion(ty) => value.to_owned(),
+            JsonType::Object(name) => format!("{}.from_dict({})", name, value),
+            //  `fromisoformat` reads a `Z` offset since Python 3.11.
+            JsonType::DateTime(date_time) => {
+                format!(
+                    "{}.fromisoformat({})",
+                    Self::get_datetime(*date_time),
+                    value
+                )
+            }
+            JsonType::Array(ty) => {
+                let item = format!("i{}", depth);
+                format!(
+                    "[{} for {} in {}]",
+                    Self::from_dict(&item, ty, depth + 1),
+                    item,
+                    value
+                )
+            }
+            JsonType::Nullable(ty) => format!(
+                "None if {0} is None else {1}",
+                value,
+                Self::from_dict(value, ty, depth)
+            ),
+            _ => value.to_owned(),
+        }
+    }
+
+    /// `value` is the expression holding the python value.
+    /// `depth` names the comprehension variables of nested lists.
+    fn to_dict(value: &str, ty: &JsonType, depth: usize) -> String {
+        match ty {
+            _ if !Self::needs_conversion(ty) => value.to_owned(),
+            JsonType::Object(_) => format!("{}.to_dict()", value),
+            JsonType::DateTime(_) => format!("{}.isoformat()", value),
+            JsonType::Array(ty) => {
+                let item = format!("i{}", depth);
+                format!(
+                    "[{} for {} in {}]",
+                    Self::to_dict(&item, ty, depth + 1),
+                    item,
+                    value
+                )
+            }
+            JsonType::Nullable(ty) => format!(
+                "None if {0} is None else {1}",
+                value,
+                Self::to_dict(value, ty, depth)
+            ),
+            _ => value.to_owned(),
+        }
+    }
+
+    fn push_field(&mut self, key: &str, ty: JsonType, optional: bool) {
+        //  Missing and `null` are the same thing here.
+        let ty = match ty {
+            JsonType::Nullable(ty) if optional => *ty,
+            ty => ty,
+        };
+        let annotation = match (&ty, optional) {
+            (JsonType::Null, _) | (_, false) => self.get_type(&ty),
+            (ty, true) => self.get_type(&JsonType::Nullable(Box::new(ty.clone()))),
+        };
+
+        let s = self.get_current();
+        let name = naming::unique(&naming::identifier(key), &mut s.names, |name| {
+            if KEYWORDS.contains(&name.as_str()) {
+                name + "_"
+            } else {
+                name
+            }
+        });
+        s.fields.push(Field {
+            name,
+            key: String::from(key),
+            annotation,
+            ty,
+            optional,
+        });
+    }
+
+    fn get_number(&self, num: Number) -> &'static str {
+        match num {
+            Number::Int => "int",
+            Number::Float => "float",
+        }
+    }
+}
+
+impl TypeAccumulator for PythonDataclassAccumulator {
+    fn end(&mut self) -> String {
+        let mut end_str = String::from("from dataclasses import dataclass\n");
+        if !self.datetime_list.is_empty() {
+            self.datetime_list.sort();
+            end_str += &format!("from datetime import {}\n", self.datetime_list.join(", "));
+        }
+
+        let mut typing = String::from("Any");
+        if self.use_optional {
+            typing += ", Optional";
+        }
+        if self.use_union {
+            typing += ", Union";
+        }
+        end_str += &format!("from typing import {}\n\n\n", typing);
+
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        end_str
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        self.push_field(key, JsonType::Number(number), false);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Boolean, false);
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::String, false);
+        Ok(())
+    }
+
+    /// Kept as ISO 8601 strings in the dict, as JSON has no dates.
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        self.push_field(key, JsonType::DateTime(date_time), false);
+        Ok(())
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Null, false);
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, JsonType::Array(Box::new(ty)), false);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, JsonType::Nullable(Box::new(ty)), false);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        self.push_field(key, JsonType::Union(tys), false);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, ty, true);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Object(String::from(object_name)), false);
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.struct_stack.push(StructItem {
+            name: String::from(object_name),
+            fields: vec![],
+            names: HashSet::from([String::from("from_dict"), String::from("to_dict")]),
+        });
+        Ok(())
+    }
+
+    /// Fields with defaults must come last, which `from_dict` does not mind since it passes
+    /// everything by name.
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        let mut s = self.struct_stack.pop().unwrap();
+        s.fields.sort_by_key(|field| field.optional);
+
+        let mut acc = format!("@dataclass\nclass {}:\n", s.name);
+        s.fields.iter().for_each(|field| {
+            let default = if field.optional { " = None" } else { "" };
+            acc += &format!("\t{}: {}{}\n", field.name, field.annotation, default);
+        });
+
+        acc += &format!(
+            "\n\t@classmethod\n\tdef from_dict(cls, d: dict[str, Any]) -> \"{}\":\n\t\treturn cls(",
+            s.name
+        );
+        s.fields.iter().for_each(|field| {
+            let key = naming::escape(&field.key, &['"']);
+            let value = if field.optional {
+                format!("d.get(\"{}\")", key)
+            } else {
+                format!("d[\"{}\"]", key)
+            };
+            let ty = match field.optional {
+                true => JsonType::Nullable(Box::new(field.ty.clone())),
+                false => field.ty.clone(),
+            };
+            acc += &format!(
+                "\n\t\t\t{}={},",
+                field.name,
+                Self::from_dict(&value, &ty, 0)
+            );
+        });
+        acc += "\n\t\t)\n";
+
+        acc += "\n\tdef to_dict(self) -> dict[str, Any]:\n\t\td: dict[str, Any] = {";
+        s.fields
+            .iter()
+            .filter(|field| !field.optional)
+            .for_each(|field| {
+                acc += &format!(
+                    "\n\t\t\t\"{}\": {},",
+                    naming::escape(&field.key, &['"']),
+                    Self::to_dict(&format!("self.{}", field.name), &field.ty, 0)
+                );
+            });
+        acc += "\n\t\t}\n";
+        //  Missing keys stay missing.
+        s.fields
+            .iter()
+            .filter(|field| field.optional)
+            .for_each(|field| {
+                acc += &format!(
+                    "\t\tif self.{0} is not None:\n\t\t\td[\"{1}\"] = {2}\n",
+                    field.name,
+                    naming::escape(&field.key, &['"']),
+                    Self::to_dict(&format!("self.{}", field.name), &field.ty, 0)
+                );
+            });
+        acc += "\t\treturn d\n\n\n";
+
+        self.done_list.push(acc);
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(&ty);
+        self.done_list.push(format!("{} = {}\n\n\n", name, ty));
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &["Any", "Optional", "Union"]
+    }
+}
@@ -1,9 +1,68 @@
 use super::*;
 
+/// Keywords that can be used as raw identifiers, such as `r#type`.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be raw identifiers.
+const RESERVED: &[&str] = &["_", "crate", "self", "Self", "super"];
+
+/// Turns a field into the key serde expects for it.
+type RenameRule = fn(&str) -> String;
+
+/// The `rename_all` rules that serde applies to fields, as serde implements them.
+const RENAME_ALL: &[(&str, RenameRule)] = &[
+    ("camelCase", |field| {
+        let mut camel = pascal_rule(field);
+        if let Some(first) = camel.get_mut(..1) {
+            first.make_ascii_lowercase();
+        }
+        camel
+    }),
+    ("PascalCase", pascal_rule),
+    ("kebab-case", |field| field.replace('_', "-")),
+    ("SCREAMING_SNAKE_CASE", |field| field.to_ascii_uppercase()),
+];
+
+fn pascal_rule(field: &str) -> String {
+    let mut capitalize = true;
+    field.chars().fold(String::new(), |mut acc, c| {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            acc.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            acc.push(c);
+        }
+        acc
+    })
+}
+
+struct Field {
+    name: String,
+    key: String,
+    ty: String,
+    default: bool,
+}
+
+struct StructItem {
+    name: String,
+    fields: Vec<Field>,
+    /// Field names taken so far.
+    names: HashSet<String>,
+}
+
 #[derive(Default)]
 pub struct RustAccumulator {
-    use_unknown: bool,
-    struct_stack: Vec<String>,
+    snake_case: bool,
+    union_list: Vec<String>,
+    struct_stack: Vec<StructItem>,
     done_list: Vec<String>,
 }
 
@@ -12,22 +71,80 @@ impl RustAccumulator {
         RustAccumulator::default()
     }
 
-    fn get_current(&mut self) -> &mut String {
+    fn get_current(&mut self) -> &mut StructItem {
         self.struct_stack.last_mut().unwrap()
     }
 
     fn get_type(&mut self, ty: JsonType) -> String {
         match ty {
-            JsonType::Null => {
-                self.use_unknown = true;
-                String::from("Unknown")
-            }
+            JsonType::Null => String::from("Option<serde_json::Value>"),
             JsonType::Number(n) => String::from(self.get_number(n)),
             JsonType::Boolean => String::from("bool"),
             JsonType::String => String::from("String"),
-            JsonType::Object(ty) => ty,
-            JsonType::Array(ty) => format!("Vec<{}>", self.get_type(*ty)),
+            JsonType::DateTime(_) => String::from("toml::value::Datetime"),
+            JsonType::Object(ty) => self.get_object(ty),
+            JsonType::Array(ty) => match *ty {
+                //  `Vec` already keeps its items on the heap.
+                JsonType::Object(ty) => format!("Vec<{}>", ty),
+                ty => format!("Vec<{}>", self.get_type(ty)),
+            },
+            JsonType::Nullable(ty) => format!("Option<{}>", self.get_type(*ty)),
+            JsonType::Union(tys) => self.get_union(tys),
+        }
+    }
+
+    /// A struct that contains itself, directly or not, would have an infinite size,
+    /// so structs that are still being declared are boxed.
+    fn get_object(&self, name: String) -> String {
+        if self.struct_stack.iter().any(|s| s.name == name) {
+            format!("Box<{}>", name)
+        } else {
+            name
+        }
+    }
+
+    /// Declares an untagged enum for the union the first time it is seen.
+    fn get_union(&mut self, tys: Vec<JsonType>) -> String {
+        let name = union_name(&tys);
+        if !self.union_list.contains(&name) {
+            self.union_list.push(name.clone());
+            let mut acc = format!(
+                "#[derive(Serialize, Deserialize)]\n#[serde(untagged)]\npub enum {} {{\n",
+                name
+            );
+            for ty in tys {
+                let variant = union_variant_name(&ty);
+                acc += &format!("\t{}({}),\n", variant, self.get_type(ty));
+            }
+            acc += "}\n\n";
+            self.done_list.push(acc);
         }
+        name
+    }
+
+    /// Fields that differ from their keys are renamed back with serde once the struct is done.
+    fn push_field(&mut self, key: &str, ty: &str, default: bool) {
+        let ident = if self.snake_case {
+            naming::identifier(&naming::snake_case(key))
+        } else {
+            naming::identifier(key)
+        };
+        let acc = self.get_current();
+        let name = naming::unique(&ident, &mut acc.names, |ident| {
+            if RESERVED.contains(&ident.as_str()) {
+                ident + "_"
+            } else if KEYWORDS.contains(&ident.as_str()) {
+                format!("r#{}", ident)
+            } else {
+                ident
+            }
+        });
+        acc.fields.push(Field {
+            name,
+            key: String::from(key),
+            ty: String::from(ty),
+            default,
+        });
     }
 
     fn get_number(&self, num: Number) -> &'static str {
@@ -42,10 +159,6 @@ impl TypeAccumulator for RustAccumulator {
     fn end(&mut self) -> String {
         let mut end_str = String::new();
 
-        if self.use_unknown {
-            end_str += "type Unknown = Option<()>;\n\n"
-        }
-
         self.done_list.iter().for_each(|done| end_str += done);
 
         end_str
@@ -53,62 +166,133 @@ impl TypeAccumulator for RustAccumulator {
 
     fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
         let num_ty = self.get_number(number);
-        let acc = self.get_current();
-        *acc += &format!("\t{}: {},\n", key, num_ty);
+        self.push_field(key, num_ty, false);
         Ok(())
     }
 
     fn boolean(&mut self, key: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        *acc += &format!("\t{}: bool,\n", key);
+        self.push_field(key, "bool", false);
         Ok(())
     }
 
     fn string(&mut self, key: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        *acc += &format!("\t{}: String,\n", key);
+        self.push_field(key, "String", false);
+        Ok(())
+    }
+
+    /// Dates and times only come from TOML, so they are left to the `toml` crate,
+    /// which keeps every kind of them apart.
+    fn date_time(&mut self, key: &str, _date_time: DateTime) -> Result<(), Error> {
+        self.push_field(key, "toml::value::Datetime", false);
         Ok(())
     }
 
     fn unknown(&mut self, key: &str) -> Result<(), Error> {
-        self.use_unknown = true;
-        let acc = self.get_current();
-        *acc += &format!("\t{}: Unknown,\n", key);
+        self.push_field(key, "Option<serde_json::Value>", false);
         Ok(())
     }
 
     fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::Array(Box::new(ty)));
+        self.push_field(key, &ty, false);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
         let ty = self.get_type(ty);
-        let acc = self.get_current();
-        *acc += &format!("\t{}: Vec<{}>,\n", key, ty);
+        self.push_field(key, &format!("Option<{}>", ty), false);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        let ty = self.get_union(tys);
+        self.push_field(key, &ty, false);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        //  Missing and `null` both become `None`.
+        let ty = match ty {
+            JsonType::Null | JsonType::Nullable(_) => self.get_type(ty),
+            ty => format!("Option<{}>", self.get_type(ty)),
+        };
+        self.push_field(key, &ty, true);
         Ok(())
     }
 
     fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        *acc += &format!("\t{}: {},\n", key, object_name);
+        let ty = self.get_object(String::from(object_name));
+        self.push_field(key, &ty, false);
         Ok(())
     }
 
     fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
-        self.struct_stack.push(String::new());
-        let acc = self.get_current();
-        *acc += &format!(
-            "#[derive(Serialize, Deserialize)]\npub struct {} {{\n",
-            object_name
-        );
+        self.struct_stack.push(StructItem {
+            name: String::from(object_name),
+            fields: vec![],
+            names: HashSet::new(),
+        });
         Ok(())
     }
 
     fn pop_object_type(&mut self) -> Result<(), Error> {
-        let acc = self.get_current();
-        *acc += "}\n\n";
         let s = self.struct_stack.pop().unwrap();
-        self.done_list.push(s);
+        let is_renamed = |field: &Field| field.name.trim_start_matches("r#") != field.key;
+
+        //  Prefer one `rename_all` over renaming fields one by one.
+        let rename_all = RENAME_ALL.iter().find(|(_, rule)| {
+            s.fields.iter().any(is_renamed)
+                && s.fields
+                    .iter()
+                    .all(|field| rule(field.name.trim_start_matches("r#")) == field.key)
+        });
+
+        let mut acc = String::from("#[derive(Serialize, Deserialize)]\n");
+        if let Some((rule, _)) = rename_all {
+            acc += &format!("#[serde(rename_all = \"{}\")]\n", rule);
+        }
+        acc += &format!("pub struct {} {{\n", s.name);
+        s.fields.iter().for_each(|field| {
+            if field.default {
+                acc += "\t#[serde(default)]\n";
+            }
+            if rename_all.is_none() && is_renamed(field) {
+                acc += &format!(
+                    "\t#[serde(rename = \"{}\")]\n",
+                    naming::escape(&field.key, &['"'])
+                );
+            }
+            acc += &format!("\t{}: {},\n", field.name, field.ty);
+        });
+        acc += "}\n\n";
+        self.done_list.push(acc);
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.done_list
+            .push(format!("pub type {} = {};\n\n", name, ty));
         Ok(())
     }
 
     fn prefered_object_name(&self) -> String {
         String::from("_Type")
     }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &[
+            "Option",
+            "String",
+            "Vec",
+            "Box",
+            "Self",
+            "Serialize",
+            "Deserialize",
+        ]
+    }
+
+    fn use_idiomatic_casing(&mut self) {
+        self.snake_case = true;
+    }
 }
@@ -0,0 +1,260 @@
+use super::*;
+
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "case",
+    "catch",
+    "class",
+    "def",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "for",
+    "given",
+    "if",
+    "implicit",
+    "import",
+    "lazy",
+    "match",
+    "new",
+    "null",
+    "object",
+    "override",
+    "package",
+    "private",
+    "protected",
+    "return",
+    "sealed",
+    "super",
+    "then",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "val",
+    "var",
+    "while",
+    "with",
+    "yield",
+];
+
+struct StructItem {
+    acc: String,
+    name: String,
+    is_empty: bool,
+}
+
+#[derive(Default)]
+pub struct ScalaAccumulator {
+    use_json: bool,
+    /// Types used from `java.time`.
+    time_list: Vec<&'static str>,
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<String>,
+}
+
+impl ScalaAccumulator {
+    pub fn begin() -> Self {
+        ScalaAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut StructItem {
+        self.struct_stack.last_mut().unwrap()
+    }
+
+    fn get_type(&mut self, ty: JsonType) -> String {
+        match ty {
+            JsonType::Null | JsonType::Union(_) => {
+                self.use_json = true;
+                String::from("Json")
+            }
+            JsonType::Number(n) => String::from(self.get_number(n)),
+            JsonType::Boolean => String::from("Boolean"),
+            JsonType::String => String::from("String"),
+            JsonType::DateTime(date_time) => {
+                let ty = match date_time {
+                    DateTime::Offset => "OffsetDateTime",
+                    DateTime::Local => "LocalDateTime",
+                    DateTime::Date => "LocalDate",
+                    DateTime::Time => "LocalTime",
+                };
+                if !self.time_list.contains(&ty) {
+                    self.time_list.push(ty);
+                }
+                String::from(ty)
+            }
+            JsonType::Object(ty) => ty,
+            JsonType::Array(ty) => format!("List[{}]", self.get_type(*ty)),
+            JsonType::Nullable(ty) => format!("Option[{}]", self.get_type(*ty)),
+        }
+    }
+
+    /// Semi-auto derivation uses field names as keys, so other keys are wrapped in backticks.
+    /// The few keys that cannot be are made into identifiers instead.
+    fn get_field(key: &str) -> String {
+        if naming::is_identifier(key) && !KEYWORDS.contains(&key) {
+            key.to_owned()
+        } else if !key.is_empty() && !key.contains(['`', '\n', '\r']) {
+            format!("`{}`", key)
+        } else {
+            format!("`{}`", naming::identifier(key))
+        }
+    }
+
+    fn push_field(&mut self, key: &str, ty: &str) {
+        let acc = self.get_current();
+        acc.acc += &format!("\t{}: {},\n", Self::get_field(key), ty);
+        acc.is_empty = false;
+    }
+
+    fn get_number(&self, num: Number) -> &'static str {
+        match num {
+            Number::Int => "Long",
+            Number::Float => "Double",
+        }
+    }
+}
+
+impl TypeAccumulator for ScalaAccumulator {
+    fn end(&mut self) -> String {
+        let mut end_str = String::new();
+
+        if self.use_json {
+            end_str += "import io.circe.{Decoder, Encoder, Json}\n";
+        } else {
+            end_str += "import io.circe.{Decoder, Encoder}\n";
+        }
+        end_str += "import io.circe.generic.semiauto.{deriveDecoder, deriveEncoder}\n";
+        self.time_list.sort();
+        match self.time_list.as_slice() {
+            [] => {}
+            [ty] => end_str += &format!("import java.time.{}\n", ty),
+            tys => end_str += &format!("import java.time.{{{}}}\n", tys.join(", ")),
+        }
+        end_str += "\n";
+
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        end_str
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        let num_ty = self.get_number(number);
+        self.push_field(key, num_ty);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, "Boolean");
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, "String");
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::DateTime(date_time));
+        self.push_field(key, &ty);
+        Ok(())
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        self.use_json = true;
+        self.push_field(key, "Json");
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.push_field(key, &format!("List[{}]", ty));
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.push_field(key, &format!("Option[{}]", ty));
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::Union(tys));
+        self.push_field(key, &ty);
+        Ok(())
+    }
+
+    /// Circe decodes a missing key as `None`.
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = match ty {
+            JsonType::Null | JsonType::Nullable(_) => self.get_type(ty),
+            ty => self.get_type(JsonType::Nullable(Box::new(ty))),
+        };
+        self.push_field(key, &ty);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        self.push_field(key, object_name);
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.struct_stack.push(StructItem {
+            acc: format!("final case class {}(\n", object_name),
+            name: String::from(object_name),
+            is_empty: true,
+        });
+        Ok(())
+    }
+
+    /// The companion object holds the codecs so they are found implicitly.
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        let mut s = self.struct_stack.pop().unwrap();
+        if s.is_empty {
+            //  Declare `X()` on one line.
+            s.acc.pop();
+        }
+        s.acc += &format!(
+            ")\n\nobject {0} {{\n\timplicit val decoder: Decoder[{0}] = deriveDecoder[{0}]\n\timplicit val encoder: Encoder[{0}] = deriveEncoder[{0}]\n}}\n\n",
+            s.name
+        );
+        self.done_list.push(s.acc);
+        Ok(())
+    }
+
+    /// Top level type aliases need Scala 3.
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.done_list.push(format!("type {} = {}\n\n", name, ty));
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &[
+            "Long",
+            "Double",
+            "Boolean",
+            "String",
+            "List",
+            "Option",
+            "Json",
+            "Decoder",
+            "Encoder",
+            "OffsetDateTime",
+            "LocalDateTime",
+            "LocalDate",
+            "LocalTime",
+        ]
+    }
+}
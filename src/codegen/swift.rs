@@ -1,9 +1,76 @@
 use super::*;
 
+const KEYWORDS: &[&str] = &[
+    "Any",
+    "Self",
+    "as",
+    "associatedtype",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "precedencegroup",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+struct StructItem {
+    acc: String,
+    /// Field names and the keys they came from.
+    keys: Vec<(String, String)>,
+    /// Field names taken so far.
+    names: HashSet<String>,
+}
+
 #[derive(Default)]
 pub struct SwiftAccumulator {
-    use_unknown: bool,
-    struct_stack: Vec<String>,
+    camel_case: bool,
+    union_list: Vec<String>,
+    struct_stack: Vec<StructItem>,
     done_list: Vec<String>,
 }
 
@@ -12,24 +79,95 @@ impl SwiftAccumulator {
         SwiftAccumulator::default()
     }
 
-    fn get_current(&mut self) -> &mut String {
+    fn get_current(&mut self) -> &mut StructItem {
         self.struct_stack.last_mut().unwrap()
     }
 
     fn get_type(&mut self, ty: JsonType) -> String {
         match ty {
-            JsonType::Null => {
-                self.use_unknown = true;
-                String::from("Unknown")
-            }
+            JsonType::Null => String::from("Never?"),
             JsonType::Number(n) => String::from(self.get_number(n)),
             JsonType::Boolean => String::from("Bool"),
             JsonType::String => String::from("String"),
+            JsonType::DateTime(DateTime::Offset) => String::from("Date"),
+            JsonType::DateTime(_) => String::from("String"),
             JsonType::Object(ty) => ty,
             JsonType::Array(ty) => format!("[{}]", self.get_type(*ty)),
+            JsonType::Nullable(ty) => format!("{}?", self.get_type(*ty)),
+            JsonType::Union(tys) => self.get_union(tys),
         }
     }
 
+    /// Declares an enum with a custom `Codable` implementation the first time the union is seen.
+    /// Decoding tries each case in order.
+    fn get_union(&mut self, tys: Vec<JsonType>) -> String {
+        let name = union_name(&tys);
+        if !self.union_list.contains(&name) {
+            self.union_list.push(name.clone());
+            let cases = tys
+                .into_iter()
+                .map(|ty| {
+                    let variant = union_variant_name(&ty);
+                    let mut case = variant.chars();
+                    let case = case
+                        .next()
+                        .map(|c| c.to_lowercase().chain(case).collect::<String>())
+                        .unwrap_or_default();
+                    (case, self.get_type(ty))
+                })
+                .collect::<Vec<_>>();
+
+            let mut acc = format!("enum {}: Codable {{\n", name);
+            cases.iter().for_each(|(case, ty)| {
+                acc += &format!("\tcase {}({})\n", case, ty);
+            });
+            acc += "\n\tinit(from decoder: Decoder) throws {\n";
+            acc += "\t\tlet container = try decoder.singleValueContainer()\n";
+            cases.iter().for_each(|(case, ty)| {
+                acc += &format!(
+                    "\t\tif let value = try? container.decode({}.self) {{\n\t\t\tself = .{}(value)\n\t\t\treturn\n\t\t}}\n",
+                    ty, case
+                );
+            });
+            acc += &format!(
+                "\t\tthrow DecodingError.typeMismatch({0}.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: \"Unexpected type for {0}\"))\n\t}}\n",
+                name
+            );
+            acc += "\n\tfunc encode(to encoder: Encoder) throws {\n";
+            acc += "\t\tvar container = encoder.singleValueContainer()\n";
+            acc += "\t\tswitch self {\n";
+            cases.iter().for_each(|(case, _)| {
+                acc += &format!(
+                    "\t\tcase .{}(let value):\n\t\t\ttry container.encode(value)\n",
+                    case
+                );
+            });
+            acc += "\t\t}\n\t}\n}\n\n";
+            self.done_list.push(acc);
+        }
+        name
+    }
+
+    /// Keywords are escaped with backticks.
+    /// Fields that differ from their keys are mapped back with `CodingKeys`.
+    fn push_field(&mut self, key: &str, ty: &str) {
+        let ident = if self.camel_case {
+            naming::identifier(&naming::camel_case(key))
+        } else {
+            naming::identifier(key)
+        };
+        let acc = self.get_current();
+        let field = naming::unique(&ident, &mut acc.names, |ident| {
+            if KEYWORDS.contains(&ident.as_str()) {
+                format!("`{}`", ident)
+            } else {
+                ident
+            }
+        });
+        acc.acc += &format!("\tvar {}: {}\n", field, ty);
+        acc.keys.push((field, String::from(key)));
+    }
+
     fn get_number(&self, num: Number) -> &'static str {
         match num {
             Number::Int => "Int",
@@ -42,20 +180,12 @@ impl TypeAccumulator for SwiftAccumulator {
     fn end(&mut self) -> String {
         let mut end_str = String::new();
 
-        if self.use_unknown {
-            end_str += "struct Unknown {}\n\n"
-        }
-
         self.done_list.iter().for_each(|done| end_str += done);
 
         end_str += r#"//  let encoder = JSONEncoder()
-//  To use this option, replace snake case members to camel case.
-//  encoder.keyEncodingStrategy = .convertToSnakeCase
 //  let json = encoder.encode(T)
 //
 //  let decoder = JSONDecoder()
-//  To use this option, replace snake case members to camel case.
-//  decoder.keyDecodingStrategy = .convertFromSnakeCase
 //  let ty = try decoder.decode(T.self, from: json)"#;
 
         end_str
@@ -63,59 +193,131 @@ impl TypeAccumulator for SwiftAccumulator {
 
     fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
         let num_ty = self.get_number(number);
-        let acc = self.get_current();
-        *acc += &format!("\tvar {}: {} \n", key, num_ty);
+        self.push_field(key, num_ty);
         Ok(())
     }
 
     fn boolean(&mut self, key: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        *acc += &format!("\tvar {}: Bool\n", key);
+        self.push_field(key, "Bool");
         Ok(())
     }
 
     fn string(&mut self, key: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        *acc += &format!("\tvar {}: String\n", key);
+        self.push_field(key, "String");
+        Ok(())
+    }
+
+    /// `JSONDecoder` needs `dateDecodingStrategy = .iso8601` to read these.
+    /// That strategy needs an offset, so other dates and times stay strings.
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        let ty = self.get_type(JsonType::DateTime(date_time));
+        self.push_field(key, &ty);
         Ok(())
     }
 
     fn unknown(&mut self, key: &str) -> Result<(), Error> {
-        self.use_unknown = true;
-        let acc = self.get_current();
-        *acc += &format!("\tvar {}: Unknown,\n", key);
+        self.push_field(key, "Never?");
         Ok(())
     }
 
     fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
         let ty = self.get_type(ty);
-        let acc = self.get_current();
-        *acc += &format!("\tvar {}: [{}]\n", key, ty);
+        self.push_field(key, &format!("[{}]", ty));
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.push_field(key, &format!("{}?", ty));
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        let ty = self.get_union(tys);
+        self.push_field(key, &ty);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = match ty {
+            JsonType::Null | JsonType::Nullable(_) => self.get_type(ty),
+            ty => format!("{}?", self.get_type(ty)),
+        };
+        self.push_field(key, &ty);
         Ok(())
     }
 
     fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
-        let acc = self.get_current();
-        *acc += &format!("\tvar {}: {}\n", key, object_name);
+        self.push_field(key, object_name);
         Ok(())
     }
 
     fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
-        self.struct_stack.push(String::new());
+        self.struct_stack.push(StructItem {
+            acc: String::new(),
+            keys: vec![],
+            names: HashSet::new(),
+        });
         let acc = self.get_current();
-        *acc += &format!("class {}: Codable {{\n", object_name);
+        acc.acc += &format!("class {}: Codable {{\n", object_name);
         Ok(())
     }
 
     fn pop_object_type(&mut self) -> Result<(), Error> {
         let acc = self.get_current();
-        *acc += "}\n\n";
+        if acc
+            .keys
+            .iter()
+            .any(|(field, key)| field.trim_matches('`') != key)
+        {
+            acc.acc += "\n\tenum CodingKeys: String, CodingKey {\n";
+            for (field, key) in &acc.keys {
+                if field.trim_matches('`') == key {
+                    acc.acc += &format!("\t\tcase {}\n", field);
+                } else {
+                    acc.acc +=
+                        &format!("\t\tcase {} = \"{}\"\n", field, naming::escape(key, &['"']));
+                }
+            }
+            acc.acc += "\t}\n";
+        }
+        acc.acc += "}\n\n";
         let s = self.struct_stack.pop().unwrap();
-        self.done_list.push(s);
+        self.done_list.push(s.acc);
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = self.get_type(ty);
+        self.done_list
+            .push(format!("typealias {} = {}\n\n", name, ty));
         Ok(())
     }
 
     fn prefered_object_name(&self) -> String {
         String::from("_Type")
     }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &[
+            "String",
+            "Int",
+            "Double",
+            "Bool",
+            "Date",
+            "Never",
+            "Any",
+            "Self",
+            "Codable",
+            "CodingKey",
+            "CodingKeys",
+            "Decoder",
+            "Encoder",
+            "DecodingError",
+        ]
+    }
+
+    fn use_idiomatic_casing(&mut self) {
+        self.camel_case = true;
+    }
 }
@@ -21,8 +21,39 @@ impl TypescriptAccumulator {
             JsonType::Number(_) => String::from("number"),
             JsonType::Boolean => String::from("boolean"),
             JsonType::String => String::from("string"),
+            //  `JSON.parse` leaves them as ISO 8601 strings.
+            JsonType::DateTime(_) => String::from("string"),
             JsonType::Object(ty) => ty,
-            JsonType::Array(ty) => format!("{}[]", Self::get_type(*ty)),
+            JsonType::Array(ty) => format!("{}[]", Self::get_element_type(*ty)),
+            JsonType::Nullable(ty) => format!("{} | null", Self::get_type(*ty)),
+            JsonType::Union(tys) => {
+                let mut variants: Vec<String> = vec![];
+                tys.into_iter().for_each(|ty| {
+                    //  Ints and floats are both `number`.
+                    let ty = Self::get_element_type(ty);
+                    if !variants.contains(&ty) {
+                        variants.push(ty);
+                    }
+                });
+                variants.join(" | ")
+            }
+        }
+    }
+
+    /// Keys that are not valid identifiers are quoted.
+    fn get_key(key: &str) -> String {
+        if naming::is_identifier(key) {
+            key.to_owned()
+        } else {
+            format!("\"{}\"", naming::escape(key, &['"']))
+        }
+    }
+
+    /// Array elements that are unions need parentheses.
+    fn get_element_type(ty: JsonType) -> String {
+        match ty {
+            JsonType::Nullable(_) | JsonType::Union(_) => format!("({})", Self::get_type(ty)),
+            ty => Self::get_type(ty),
         }
     }
 }
@@ -38,38 +69,65 @@ impl TypeAccumulator for TypescriptAccumulator {
 
     fn number(&mut self, key: &str, _: Number) -> Result<(), Error> {
         let acc = self.get_current();
-        *acc += &format!("\t{}: number;\n", key);
+        *acc += &format!("\t{}: number;\n", Self::get_key(key));
         Ok(())
     }
 
     fn boolean(&mut self, key: &str) -> Result<(), Error> {
         let acc = self.get_current();
-        *acc += &format!("\t{}: boolean;\n", key);
+        *acc += &format!("\t{}: boolean;\n", Self::get_key(key));
         Ok(())
     }
 
     fn string(&mut self, key: &str) -> Result<(), Error> {
         let acc = self.get_current();
-        *acc += &format!("\t{}: string;\n", key);
+        *acc += &format!("\t{}: string;\n", Self::get_key(key));
+        Ok(())
+    }
+
+    fn date_time(&mut self, key: &str, _date_time: DateTime) -> Result<(), Error> {
+        let acc = self.get_current();
+        *acc += &format!("\t{}: string;\n", Self::get_key(key));
         Ok(())
     }
 
     fn unknown(&mut self, key: &str) -> Result<(), Error> {
         let acc = self.get_current();
-        *acc += &format!("\t{}: unknown;\n", key);
+        *acc += &format!("\t{}: unknown;\n", Self::get_key(key));
         Ok(())
     }
 
     fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = Self::get_element_type(ty);
+        let acc = self.get_current();
+        *acc += &format!("\t{}: {}[];\n", Self::get_key(key), ty);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
         let ty = Self::get_type(ty);
         let acc = self.get_current();
-        *acc += &format!("\t{}: {}[];\n", key, ty);
+        *acc += &format!("\t{}: {} | null;\n", Self::get_key(key), ty);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        let ty = Self::get_type(JsonType::Union(tys));
+        let acc = self.get_current();
+        *acc += &format!("\t{}: {};\n", Self::get_key(key), ty);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = Self::get_type(ty);
+        let acc = self.get_current();
+        *acc += &format!("\t{}?: {};\n", Self::get_key(key), ty);
         Ok(())
     }
 
     fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
         let acc = self.get_current();
-        *acc += &format!("\t{}: {};\n", key, object_name);
+        *acc += &format!("\t{}: {};\n", Self::get_key(key), object_name);
         Ok(())
     }
 
@@ -88,7 +146,17 @@ impl TypeAccumulator for TypescriptAccumulator {
         Ok(())
     }
 
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let ty = Self::get_type(ty);
+        self.done_list.push(format!("type {} = {};\n\n", name, ty));
+        Ok(())
+    }
+
     fn prefered_object_name(&self) -> String {
         String::from("_Type")
     }
+
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &["Date"]
+    }
 }
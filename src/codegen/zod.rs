@@ -0,0 +1,226 @@
+use super::*;
+
+struct StructItem {
+    acc: String,
+    name: String,
+    /// The TypeScript type, which is only written out if the schema refers to itself.
+    ty: String,
+    recursive: bool,
+}
+
+#[derive(Default)]
+pub struct ZodAccumulator {
+    struct_stack: Vec<StructItem>,
+    done_list: Vec<String>,
+}
+
+impl ZodAccumulator {
+    pub fn begin() -> Self {
+        ZodAccumulator::default()
+    }
+
+    fn get_current(&mut self) -> &mut StructItem {
+        self.struct_stack.last_mut().unwrap()
+    }
+
+    fn get_schema(&mut self, ty: JsonType) -> String {
+        match ty {
+            JsonType::Null => String::from("z.unknown()"),
+            JsonType::Number(Number::Int) => String::from("z.number().int()"),
+            JsonType::Number(Number::Float) => String::from("z.number()"),
+            JsonType::Boolean => String::from("z.boolean()"),
+            JsonType::String => String::from("z.string()"),
+            JsonType::DateTime(DateTime::Offset) => {
+                String::from("z.string().datetime({ offset: true })")
+            }
+            JsonType::DateTime(DateTime::Local) => {
+                String::from("z.string().datetime({ local: true })")
+            }
+            JsonType::DateTime(DateTime::Date) => String::from("z.string().date()"),
+            JsonType::DateTime(DateTime::Time) => String::from("z.string().time()"),
+            JsonType::Object(ty) => self.get_object_schema(ty),
+            JsonType::Array(ty) => format!("z.array({})", self.get_schema(*ty)),
+            JsonType::Nullable(ty) => format!("{}.nullable()", self.get_schema(*ty)),
+            JsonType::Union(tys) => {
+                let mut variants: Vec<String> = vec![];
+                tys.into_iter().for_each(|ty| {
+                    let ty = self.get_schema(ty);
+                    if !variants.contains(&ty) {
+                        variants.push(ty);
+                    }
+                });
+                match variants.len() {
+                    1 => variants.remove(0),
+                    _ => format!("z.union([{}])", variants.join(", ")),
+                }
+            }
+        }
+    }
+
+    /// Schemas that are still being built are not initialized yet, so they are
+    /// referred to lazily.
+    fn get_object_schema(&mut self, name: String) -> String {
+        match self.struct_stack.iter_mut().find(|s| s.name == name) {
+            Some(s) => {
+                s.recursive = true;
+                format!("z.lazy(() => {}Schema)", name)
+            }
+            None => name + "Schema",
+        }
+    }
+
+    fn get_type(ty: JsonType) -> String {
+        match ty {
+            JsonType::Null => String::from("unknown"),
+            JsonType::Number(_) => String::from("number"),
+            JsonType::Boolean => String::from("boolean"),
+            JsonType::String => String::from("string"),
+            JsonType::DateTime(_) => String::from("string"),
+            JsonType::Object(ty) => ty,
+            JsonType::Array(ty) => format!("Array<{}>", Self::get_type(*ty)),
+            JsonType::Nullable(ty) => format!("{} | null", Self::get_type(*ty)),
+            JsonType::Union(tys) => {
+                let mut variants: Vec<String> = vec![];
+                tys.into_iter().for_each(|ty| {
+                    let ty = Self::get_type(ty);
+                    if !variants.contains(&ty) {
+                        variants.push(ty);
+                    }
+                });
+                variants.join(" | ")
+            }
+        }
+    }
+
+    /// Keys that are not valid identifiers are quoted.
+    fn get_key(key: &str) -> String {
+        if naming::is_identifier(key) {
+            key.to_owned()
+        } else {
+            format!("\"{}\"", naming::escape(key, &['"']))
+        }
+    }
+
+    fn push_field(&mut self, key: &str, ty: JsonType, optional: bool) {
+        let key = Self::get_key(key);
+        let ts_ty = Self::get_type(ty.clone());
+        let schema = self.get_schema(ty);
+        let acc = self.get_current();
+        if optional {
+            acc.acc += &format!("\t{}: {}.optional(),\n", key, schema);
+            acc.ty += &format!("\t{}?: {};\n", key, ts_ty);
+        } else {
+            acc.acc += &format!("\t{}: {},\n", key, schema);
+            acc.ty += &format!("\t{}: {};\n", key, ts_ty);
+        }
+    }
+
+    fn declare(name: &str, schema: &str) -> String {
+        format!(
+            "export const {0}Schema = {1};\nexport type {0} = z.infer<typeof {0}Schema>;\n\n",
+            name, schema
+        )
+    }
+
+    /// The type of a schema that refers to itself cannot be inferred, so it is spelled out.
+    fn declare_recursive(name: &str, schema: &str, ty: &str) -> String {
+        format!(
+            "export type {0} = {{\n{2}}};\nexport const {0}Schema: z.ZodType<{0}> = {1};\n\n",
+            name, schema, ty
+        )
+    }
+}
+
+impl TypeAccumulator for ZodAccumulator {
+    fn end(&mut self) -> String {
+        let mut end_str = String::from("import { z } from \"zod\";\n\n");
+
+        self.done_list.iter().for_each(|done| end_str += done);
+
+        end_str
+    }
+
+    fn number(&mut self, key: &str, number: Number) -> Result<(), Error> {
+        self.push_field(key, JsonType::Number(number), false);
+        Ok(())
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Boolean, false);
+        Ok(())
+    }
+
+    fn string(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::String, false);
+        Ok(())
+    }
+
+    /// JSON has no dates, so they are checked as ISO 8601 strings.
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error> {
+        self.push_field(key, JsonType::DateTime(date_time), false);
+        Ok(())
+    }
+
+    fn unknown(&mut self, key: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Null, false);
+        Ok(())
+    }
+
+    fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, JsonType::Array(Box::new(ty)), false);
+        Ok(())
+    }
+
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, JsonType::Nullable(Box::new(ty)), false);
+        Ok(())
+    }
+
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error> {
+        self.push_field(key, JsonType::Union(tys), false);
+        Ok(())
+    }
+
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error> {
+        self.push_field(key, ty, true);
+        Ok(())
+    }
+
+    fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error> {
+        self.push_field(key, JsonType::Object(String::from(object_name)), false);
+        Ok(())
+    }
+
+    fn push_object_type(&mut self, object_name: &str) -> Result<(), Error> {
+        self.struct_stack.push(StructItem {
+            acc: String::from("z.object({\n"),
+            name: String::from(object_name),
+            ty: String::new(),
+            recursive: false,
+        });
+        Ok(())
+    }
+
+    fn pop_object_type(&mut self) -> Result<(), Error> {
+        let acc = self.get_current();
+        acc.acc += "})";
+        let s = self.struct_stack.pop().unwrap();
+        if s.recursive {
+            self.done_list
+                .push(Self::declare_recursive(&s.name, &s.acc, &s.ty));
+        } else {
+            self.done_list.push(Self::declare(&s.name, &s.acc));
+        }
+        Ok(())
+    }
+
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error> {
+        let schema = self.get_schema(ty);
+        self.done_list.push(Self::declare(name, &schema));
+        Ok(())
+    }
+
+    fn prefered_object_name(&self) -> String {
+        String::from("Type")
+    }
+}
@@ -18,6 +18,18 @@
 //! let res = res.unwrap();
 //! eprintln!("{}", res);
 //!
+//! //  Several samples of the same type can be merged into one.
+//! let mut accumulator = Box::new(RustAccumulator::begin());
+//! let res = generate_merged(accumulator.as_mut(), "MyType", &[r#"{"a": 10}"#, r#"{"b": true}"#]);
+//! let res = res.unwrap();
+//! eprintln!("{}", res);
+//!
+//! //  Or generated from a JSON Schema instead of samples.
+//! let mut accumulator = Box::new(RustAccumulator::begin());
+//! let schema = r#"{"properties": {"a": {"type": "integer"}}, "required": ["a"]}"#;
+//! let res = generate_from_schema(accumulator.as_mut(), "MyType", schema);
+//! let res = res.unwrap();
+//! eprintln!("{}", res);
 //! ```
 //!
 //! ## Contributing
@@ -26,6 +38,11 @@
 //!
 
 pub mod codegen;
+mod naming;
+mod openapi;
+mod schema;
+mod toml_input;
+mod yaml;
 
 #[cfg(test)]
 mod test;
@@ -38,11 +55,13 @@ mod test;
 ))]
 mod wasm;
 
-use json::{number::Number as JNumber, object::Object, Array, JsonValue};
-use std::collections::HashMap;
+use json::{number::Number as JNumber, Array, JsonValue};
+use std::collections::{HashMap, HashSet};
 
 pub use codegen::*;
 pub use json;
+pub use toml;
+pub use yaml_rust;
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum Number {
@@ -50,14 +69,34 @@ pub enum Number {
     Float,
 }
 
+/// The dates and times that TOML tells apart.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DateTime {
+    /// A date and time with an offset, such as `1979-05-27T07:32:00Z`.
+    Offset,
+    /// A date and time without an offset, such as `1979-05-27T07:32:00`.
+    Local,
+    /// A date alone, such as `1979-05-27`.
+    Date,
+    /// A time alone, such as `07:32:00`.
+    Time,
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum JsonType {
     Null,
     Number(Number),
     Boolean,
     String,
+    /// A date, time or both, such as TOML's `1979-05-27T07:32:00Z`.
+    DateTime(DateTime),
     Object(String),
     Array(Box<JsonType>),
+    /// Either the inner type or `null`.
+    Nullable(Box<JsonType>),
+    /// Any one of the inner types.
+    /// Only produced when [`GenerateOptions::unions`] is set.
+    Union(Vec<JsonType>),
 }
 
 pub trait TypeAccumulator {
@@ -66,56 +105,359 @@ pub trait TypeAccumulator {
     fn number(&mut self, key: &str, number: Number) -> Result<(), Error>;
     fn boolean(&mut self, key: &str) -> Result<(), Error>;
     fn string(&mut self, key: &str) -> Result<(), Error>;
+    /// Only TOML has dates and times, JSON keeps them in strings.
+    fn date_time(&mut self, key: &str, date_time: DateTime) -> Result<(), Error>;
     fn unknown(&mut self, key: &str) -> Result<(), Error>;
     fn array(&mut self, key: &str, ty: JsonType) -> Result<(), Error>;
     fn object(&mut self, key: &str, object_name: &str) -> Result<(), Error>;
+    /// A field that is sometimes `null`.
+    /// `ty` is the type of the field when it is not `null`.
+    fn nullable(&mut self, key: &str, ty: JsonType) -> Result<(), Error>;
+    /// A field that holds values of differing types.
+    fn union(&mut self, key: &str, tys: Vec<JsonType>) -> Result<(), Error>;
+    /// A field that may be missing from the object.
+    fn optional(&mut self, key: &str, ty: JsonType) -> Result<(), Error>;
 
     fn push_object_type(&mut self, object_name: &str) -> Result<(), Error>;
     fn pop_object_type(&mut self) -> Result<(), Error>;
+    /// Declare `name` as another name for `ty`.
+    /// This is used when the input is not an object, such as `[{ ... }]`.
+    fn alias(&mut self, name: &str, ty: JsonType) -> Result<(), Error>;
 
+    /// Used to name nested types whose key has nothing to build a name from.
     fn prefered_object_name(&self) -> String;
+
+    /// Type names that the output already refers to, such as `String` and `Option` in Rust.
+    /// Types named after keys skip these and are prefixed with their parent's name instead.
+    fn reserved_type_names(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Called before anything is generated with the name of the type that the whole input becomes.
+    /// Inputs that declare several types of their own, such as OpenAPI documents, have no root.
+    fn set_root_name(&mut self, _name: &str) {}
+
+    /// Rename fields to the language's usual casing, such as `snake_case` in Rust, while still
+    /// mapping them back to their keys.
+    /// Accumulators that cannot rename fields keep the keys as they are.
+    fn use_idiomatic_casing(&mut self) {}
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     /// Got a parse error from the [`json`] crate.
     Parse(json::Error),
-    /// All inputs should begin as an object `{ ... }`.
-    ExpectedObject,
-    /// A [`json`] number does not fit within a [`f64`] or [`i64`].
-    /// This error does not seem to work.
-    BadNumber,
     /// A type is not supported by the choosen language.
     TypeNotSupported,
     /// An array cannot contain multiple elements of differing types.
     DifferingArrayType,
+    /// Two samples disagree on the type of the same value.
+    DifferingSampleType,
+    /// No samples were given to merge.
+    NoSamples,
+    /// A JSON Schema `$ref` does not point within the document.
+    BadReference(String),
+    /// Got a parse error from the [`yaml_rust`] crate.
+    YamlParse(yaml_rust::ScanError),
+    /// A YAML alias refers to an anchor that is undefined or contains the alias itself.
+    YamlBadAlias,
+    /// A YAML mapping has a sequence or mapping as a key.
+    YamlKeyNotSupported,
+    /// A YAML `<<` merge key is given something other than mappings.
+    YamlBadMerge,
+    /// Got a parse error from the [`toml`] crate.
+    TomlParse(toml::de::Error),
+    /// Reading NDJSON lines failed, such as on a line that is not UTF-8.
+    Io(std::io::ErrorKind),
 }
 
 /// Goes with [`ObjectTypeTable`] to decrease ambiguity.
 type ObjectField = String;
 
+/// A field as seen by [`ObjectTypeTable`].
+/// The `bool` marks fields that are optional.
+type ObjectFieldType = (ObjectField, JsonType, bool);
+
 /// Allow prevent similar json objects from creating separate types.
 #[derive(Clone, Default)]
-struct ObjectTypeTable(HashMap<Vec<(ObjectField, JsonType)>, String>);
+struct ObjectTypeTable {
+    /// Objects usually have one type, but [`GenerateOptions::names`] can give the same fields several.
+    types: HashMap<Vec<ObjectFieldType>, Vec<String>>,
+    /// Every type name handed out so far, including the root and names that are still being generated.
+    names: HashSet<String>,
+    /// See [`GenerateOptions::names`].
+    overrides: HashMap<String, String>,
+    /// Object schemas reached through `$ref`, see [`generate_from_schema`].
+    definitions: schema::Definitions,
+    /// The type names given to [`ObjectTypeTable::definitions`] so far, by JSON pointer.
+    refs: HashMap<String, String>,
+}
 
 impl ObjectTypeTable {
+    /// Starts out with [`TypeAccumulator::reserved_type_names`] taken.
+    pub fn new(
+        accumulator: &dyn TypeAccumulator,
+        options: &GenerateOptions,
+        definitions: schema::Definitions,
+    ) -> Self {
+        ObjectTypeTable {
+            names: accumulator
+                .reserved_type_names()
+                .iter()
+                .map(|name| String::from(*name))
+                .collect(),
+            overrides: options.names.clone(),
+            definitions,
+            ..Default::default()
+        }
+    }
+
+    /// `fields` can be unsorted.
+    pub fn get_object_name(&self, mut fields: Vec<ObjectFieldType>) -> Option<String> {
+        fields.sort_by(|a, b| a.0.cmp(&b.0));
+        self.types.get(&fields)?.first().cloned()
+    }
+
     /// `fields` can be unsorted.
-    pub fn get_object_name(&self, mut fields: Vec<(ObjectField, JsonType)>) -> Option<String> {
+    pub fn has_object_name(&self, mut fields: Vec<ObjectFieldType>, object_name: &str) -> bool {
         fields.sort_by(|a, b| a.0.cmp(&b.0));
-        self.0.get(&fields).cloned()
+        self.types
+            .get(&fields)
+            .is_some_and(|names| names.iter().any(|name| name == object_name))
     }
 
     /// `fields` can be unsorted.
-    pub fn insert(&mut self, object_name: &str, mut fields: Vec<(ObjectField, JsonType)>) {
+    pub fn insert(&mut self, object_name: &str, mut fields: Vec<ObjectFieldType>) {
         fields.sort_by(|a, b| a.0.cmp(&b.0));
-        self.0.insert(fields, String::from(object_name));
+        self.types
+            .entry(fields)
+            .or_default()
+            .push(String::from(object_name));
     }
 
     pub fn count(&self) -> usize {
-        self.0.len()
+        self.types.len()
+    }
+
+    /// Take the first free name out of `candidates`, counting up from the last one if all are taken.
+    pub fn reserve_name(&mut self, candidates: &[String]) -> String {
+        let name = candidates
+            .iter()
+            .find(|name| !self.names.contains(*name))
+            .cloned()
+            .unwrap_or_else(|| {
+                let last = candidates.last().unwrap();
+                (2..)
+                    .map(|n| format!("{}{}", last, n))
+                    .find(|name| !self.names.contains(name))
+                    .unwrap()
+            });
+        self.names.insert(name.clone());
+        name
+    }
+
+    /// Give back a name from [`ObjectTypeTable::reserve_name`] that ended up unused.
+    pub fn release_name(&mut self, name: &str) {
+        self.names.remove(name);
+    }
+}
+
+/// Where an object was found, used to name its type.
+#[derive(Clone, Copy)]
+struct TypePath<'a> {
+    /// The name of the type that holds the object.
+    parent: &'a str,
+    /// The JSONPath of the object that holds the object.
+    parent_path: &'a str,
+    /// The key the object was found under, [`None`] for elements of a root array.
+    key: Option<&'a str>,
+    /// How many arrays deep below `key` the object is.
+    depth: usize,
+}
+
+impl<'a> TypePath<'a> {
+    fn root(name: &'a str) -> Self {
+        TypePath {
+            parent: name,
+            parent_path: "$",
+            key: None,
+            depth: 0,
+        }
+    }
+
+    fn field(parent: &'a str, parent_path: &'a str, key: &'a str) -> Self {
+        TypePath {
+            parent,
+            parent_path,
+            key: Some(key),
+            depth: 0,
+        }
+    }
+
+    fn element(self) -> Self {
+        TypePath {
+            depth: self.depth + 1,
+            ..self
+        }
+    }
+
+    /// Such as `$.data.items[*]`.
+    fn json_path(&self) -> String {
+        let mut path = String::from(self.parent_path);
+        if let Some(key) = self.key {
+            path += &naming::json_path_segment(key);
+        }
+        (0..self.depth).for_each(|_| path += "[*]");
+        path
+    }
+
+    /// Names to try in order, such as `Address` then `MyTypeAddress`.
+    /// Array elements are singularized, so `users` gives `User`.
+    /// Empty if the key has nothing to build a name from.
+    fn candidate_names(&self) -> Vec<String> {
+        let base = match self.key {
+            Some(key) if self.depth > 0 => match naming::singular(key) {
+                Some(key) => naming::pascal_case(&key),
+                None => naming::pascal_case(key) + "Item",
+            },
+            Some(key) => naming::pascal_case(key),
+            None => String::from("Item"),
+        };
+        if base.is_empty() {
+            vec![]
+        } else if self.key.is_none() || base.starts_with(|c: char| c.is_ascii_digit()) {
+            vec![format!("{}{}", self.parent, base)]
+        } else {
+            vec![base.clone(), format!("{}{}", self.parent, base)]
+        }
+    }
+}
+
+/// An inferred type that has not yet been handed to an accumulator.
+/// Samples are turned into shapes first so that several of them can be merged.
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    /// Nothing is known yet, such as the elements of an empty array.
+    Empty,
+    Null,
+    Number(Number),
+    Boolean,
+    String,
+    DateTime(DateTime),
+    Object(Vec<FieldShape>),
+    Array(Box<Shape>),
+    /// Seen as both `null` and the inner shape.
+    Nullable(Box<Shape>),
+    /// Seen as shapes that could not be merged.
+    /// No two variants are of the same kind, see [`Shape::same_kind`].
+    Union(Vec<Shape>),
+    /// An object schema named by its JSON pointer, see [`ObjectTypeTable::definitions`].
+    Ref(String),
+}
+
+/// A field of [`Shape::Object`].
+#[derive(Debug, Clone, PartialEq)]
+struct FieldShape {
+    key: ObjectField,
+    shape: Shape,
+    /// The key is missing from some of the merged objects.
+    optional: bool,
+}
+
+impl Shape {
+    /// Unify two samples of the same value, or [`None`] if they cannot be unified.
+    /// Objects keep every field seen in either sample, marking the ones missing from the other as optional.
+    /// With [`GenerateOptions::unions`], differing shapes become a [`Shape::Union`] instead.
+    fn merge(self, other: Shape, options: &GenerateOptions) -> Option<Shape> {
+        Some(match (self, other) {
+            (Shape::Empty, shape) | (shape, Shape::Empty) => shape,
+            (Shape::Null, Shape::Null) => Shape::Null,
+            (Shape::Null, shape) | (shape, Shape::Null) => shape.into_nullable(),
+            (Shape::Nullable(a), b) | (b, Shape::Nullable(a)) => {
+                a.merge(b, options)?.into_nullable()
+            }
+            (Shape::Union(mut variants), other) | (other, Shape::Union(mut variants)) => {
+                let others = match other {
+                    Shape::Union(others) => others,
+                    other => vec![other],
+                };
+                for other in others {
+                    match variants
+                        .iter_mut()
+                        .find(|variant| variant.same_kind(&other))
+                    {
+                        Some(variant) => {
+                            *variant =
+                                std::mem::replace(variant, Shape::Empty).merge(other, options)?
+                        }
+                        None => variants.push(other),
+                    }
+                }
+                Shape::Union(variants)
+            }
+            (Shape::Object(mut fields), Shape::Object(other_fields)) => {
+                fields.iter_mut().for_each(|field| {
+                    if !other_fields.iter().any(|other| other.key == field.key) {
+                        field.optional = true;
+                    }
+                });
+                for other in other_fields {
+                    match fields.iter_mut().find(|field| field.key == other.key) {
+                        Some(field) => {
+                            field.shape = std::mem::replace(&mut field.shape, Shape::Empty)
+                                .merge(other.shape, options)?;
+                            field.optional |= other.optional;
+                        }
+                        None => fields.push(FieldShape {
+                            optional: true,
+                            ..other
+                        }),
+                    }
+                }
+                Shape::Object(fields)
+            }
+            (Shape::Array(a), Shape::Array(b)) => Shape::Array(Box::new(a.merge(*b, options)?)),
+            //  Ints widen into floats.
+            (Shape::Number(a), Shape::Number(b)) if a != b => Shape::Number(Number::Float),
+            //  Dates and times widen into strings, as do differing kinds of them.
+            (Shape::DateTime(_), Shape::String) | (Shape::String, Shape::DateTime(_)) => {
+                Shape::String
+            }
+            (Shape::DateTime(a), Shape::DateTime(b)) if a != b => Shape::String,
+            (a, b) if a == b => a,
+            (a, b) if options.unions => Shape::Union(vec![a, b]),
+            _ => None?,
+        })
+    }
+
+    /// Shapes of the same kind are merged into the same union variant.
+    fn same_kind(&self, other: &Shape) -> bool {
+        match (self, other) {
+            (Shape::Ref(a), Shape::Ref(b)) => a == b,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+
+    fn into_nullable(self) -> Shape {
+        match self {
+            Shape::Empty | Shape::Null | Shape::Nullable(_) => self,
+            shape => Shape::Nullable(Box::new(shape)),
+        }
     }
 }
 
+/// Tweaks how types are inferred, see [`generate_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    /// Let values of differing types become a [`JsonType::Union`] instead of failing with
+    /// [`Error::DifferingArrayType`] or [`Error::DifferingSampleType`].
+    pub unions: bool,
+    /// Force the names of object types by their JSONPath, such as `$.data.items[*]` to `Product`.
+    /// Keys that are not plain identifiers use brackets, as in `$['first-name']`.
+    /// Objects that are not listed are named after their key.
+    pub names: HashMap<String, String>,
+}
+
 /// Generate language bindings using a provided accumulator.
 /// See the [`codegen`] for supported accumulators or build your own.
 pub fn generate(
@@ -123,131 +465,484 @@ pub fn generate(
     name: &str,
     json_str: &str,
 ) -> Result<String, Error> {
-    let val = json::parse(json_str).map_err(Error::Parse)?;
+    generate_merged(accumulator, name, &[json_str])
+}
+
+/// Like [`generate`], but with several samples of the same type.
+/// Objects are merged field by field, so the output covers every key seen across all samples.
+/// Keys that are missing from some samples become optional.
+pub fn generate_merged(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    json_strs: &[&str],
+) -> Result<String, Error> {
+    generate_with_options(accumulator, name, json_strs, &GenerateOptions::default())
+}
+
+/// Like [`generate_merged`], but with [`GenerateOptions`].
+pub fn generate_with_options(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    json_strs: &[&str],
+    options: &GenerateOptions,
+) -> Result<String, Error> {
+    let vals = json_strs
+        .iter()
+        .map(|json_str| json::parse(json_str).map_err(Error::Parse))
+        .collect::<Result<Vec<_>, _>>()?;
+    generate_values(accumulator, name, &vals, options)
+}
+
+/// Like [`generate`], but with newline-delimited JSON, also known as JSON Lines.
+/// Every line is a sample of the same type, and blank lines are skipped.
+pub fn generate_from_ndjson(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    ndjson_str: &str,
+) -> Result<String, Error> {
+    generate_from_ndjson_with_options(
+        accumulator,
+        name,
+        [ndjson_str.as_bytes()],
+        &GenerateOptions::default(),
+    )
+}
+
+/// Like [`generate_from_ndjson`], but with [`GenerateOptions`] and lines from several readers.
+/// Each line is merged as soon as it is read, so the whole input is never held at once.
+pub fn generate_from_ndjson_with_options<R: std::io::BufRead>(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    readers: impl IntoIterator<Item = R>,
+    options: &GenerateOptions,
+) -> Result<String, Error> {
+    let mut shape = Shape::Empty;
+    for reader in readers {
+        for line in reader.lines() {
+            let line = line.map_err(|e| Error::Io(e.kind()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let val = json::parse(&line).map_err(Error::Parse)?;
+            shape = shape
+                .merge(value_into_shape(&val, options)?, options)
+                .ok_or(Error::DifferingSampleType)?;
+        }
+    }
+    generate_shape(accumulator, name, shape, options, Default::default())
+}
 
-    let mut obj_table = ObjectTypeTable::default();
+/// Like [`generate`], but with a YAML document instead of JSON.
+/// A stream of several documents is merged as several samples.
+///
+/// Aliases are replaced by the value of their anchor, and `<<` merge keys are applied.
+/// Scalar keys are read as strings, so `1: a` has the key `"1"`.
+pub fn generate_from_yaml(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    yaml_str: &str,
+) -> Result<String, Error> {
+    generate_from_yaml_with_options(accumulator, name, &[yaml_str], &GenerateOptions::default())
+}
 
-    match val {
-        JsonValue::Object(val) => {
+/// Like [`generate_from_yaml`], but with [`GenerateOptions`] and documents from several streams.
+pub fn generate_from_yaml_with_options(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    yaml_strs: &[&str],
+    options: &GenerateOptions,
+) -> Result<String, Error> {
+    let mut shape = Shape::Empty;
+    for yaml_str in yaml_strs {
+        for document in yaml::yaml_into_shapes(yaml_str, options)? {
+            shape = shape
+                .merge(document, options)
+                .ok_or(Error::DifferingSampleType)?;
+        }
+    }
+    generate_shape(accumulator, name, shape, options, Default::default())
+}
+
+/// Like [`generate`], but with a TOML document instead of JSON.
+/// Dates and times become [`JsonType::DateTime`] and arrays of tables are arrays of objects.
+pub fn generate_from_toml(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    toml_str: &str,
+) -> Result<String, Error> {
+    generate_from_toml_with_options(accumulator, name, &[toml_str], &GenerateOptions::default())
+}
+
+/// Like [`generate_from_toml`], but with [`GenerateOptions`] and several samples.
+pub fn generate_from_toml_with_options(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    toml_strs: &[&str],
+    options: &GenerateOptions,
+) -> Result<String, Error> {
+    let shape = toml_strs.iter().try_fold(Shape::Empty, |shape, toml_str| {
+        shape
+            .merge(toml_input::toml_into_shape(toml_str, options)?, options)
+            .ok_or(Error::DifferingSampleType)
+    })?;
+    generate_shape(accumulator, name, shape, options, Default::default())
+}
+
+/// Samples that were already parsed are merged, then generated.
+fn generate_values(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    vals: &[JsonValue],
+    options: &GenerateOptions,
+) -> Result<String, Error> {
+    let shape = vals.iter().try_fold(Shape::Empty, |shape, val| {
+        shape
+            .merge(value_into_shape(val, options)?, options)
+            .ok_or(Error::DifferingSampleType)
+    })?;
+    generate_shape(accumulator, name, shape, options, Default::default())
+}
+
+/// Generate language bindings from a JSON Schema document instead of samples.
+/// Drafts 07 and 2020-12 are understood, including `$ref` to `$defs` or `definitions`.
+/// Objects reached through `$ref` keep the name they were defined under.
+pub fn generate_from_schema(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    schema_str: &str,
+) -> Result<String, Error> {
+    generate_from_schema_with_options(accumulator, name, schema_str, &GenerateOptions::default())
+}
+
+/// Like [`generate_from_schema`], but with [`GenerateOptions`].
+/// [`GenerateOptions::unions`] lets `oneOf` and `anyOf` become a [`JsonType::Union`], otherwise
+/// differing variants are left unknown.
+pub fn generate_from_schema_with_options(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    schema_str: &str,
+    options: &GenerateOptions,
+) -> Result<String, Error> {
+    let document = json::parse(schema_str).map_err(Error::Parse)?;
+    let (shape, definitions) = schema::schema_into_shape(&document, options)?;
+    //  A schema that allows anything, such as `{}`, still describes a value, unlike no samples.
+    let shape = match shape {
+        Shape::Empty => Shape::Null,
+        shape => shape,
+    };
+    generate_shape(accumulator, name, shape, options, definitions)
+}
+
+/// Generate language bindings for every schema in an OpenAPI 3 document.
+/// Schemas under `components/schemas` keep their names.
+/// Request and response bodies that are not just a `$ref` are named after their operation, as in
+/// `ListPetsRequest` and `ListPets200Response`.
+pub fn generate_from_openapi(
+    accumulator: &mut dyn TypeAccumulator,
+    openapi_str: &str,
+) -> Result<String, Error> {
+    generate_from_openapi_with_options(accumulator, openapi_str, &GenerateOptions::default())
+}
+
+/// Like [`generate_from_openapi`], but with [`GenerateOptions`].
+pub fn generate_from_openapi_with_options(
+    accumulator: &mut dyn TypeAccumulator,
+    openapi_str: &str,
+    options: &GenerateOptions,
+) -> Result<String, Error> {
+    let document = json::parse(openapi_str).map_err(Error::Parse)?;
+    let (shapes, definitions) = openapi::openapi_into_shapes(&document, options)?;
+    generate_shapes(accumulator, shapes, options, definitions)
+}
+
+fn generate_shape(
+    accumulator: &mut dyn TypeAccumulator,
+    name: &str,
+    shape: Shape,
+    options: &GenerateOptions,
+    definitions: schema::Definitions,
+) -> Result<String, Error> {
+    let mut obj_table = ObjectTypeTable::new(accumulator, options, definitions);
+    obj_table.reserve_name(&[String::from(name)]);
+    accumulator.set_root_name(name);
+
+    //  A root object schema is named `name`, even when it refers to itself.
+    let shape = match shape {
+        Shape::Ref(pointer) => {
+            let (_, fields) = get_definition(&obj_table, &pointer)?;
+            obj_table.refs.insert(pointer, String::from(name));
+            Shape::Object(fields)
+        }
+        shape => shape,
+    };
+
+    match shape {
+        Shape::Object(fields) => {
             accumulator.push_object_type(name)?;
-            generate_object(accumulator, &mut obj_table, &val)?;
+            generate_object(accumulator, &mut obj_table, name, "$", &fields)?;
             accumulator.pop_object_type()?;
         }
-        _ => Err(Error::ExpectedObject)?,
+        Shape::Empty => Err(Error::NoSamples)?,
+        shape => {
+            let path = TypePath::root(name);
+            let ty = shape_into_json_type(accumulator, &mut obj_table, path, &shape)?;
+            accumulator.alias(name, ty)?;
+        }
     };
 
     Ok(accumulator.end())
 }
 
+/// Like [`generate_shape`], but for several named types.
+/// Objects are generated through [`get_ref_type`] and anything else becomes an alias.
+fn generate_shapes(
+    accumulator: &mut dyn TypeAccumulator,
+    shapes: Vec<(String, Shape)>,
+    options: &GenerateOptions,
+    definitions: schema::Definitions,
+) -> Result<String, Error> {
+    let mut obj_table = ObjectTypeTable::new(accumulator, options, definitions);
+
+    //  Aliases take their names first so that nested objects cannot.
+    let shapes = shapes
+        .into_iter()
+        .map(|(name, shape)| match shape {
+            Shape::Ref(_) => (name, shape),
+            shape => (obj_table.reserve_name(&[name]), shape),
+        })
+        .collect::<Vec<_>>();
+
+    for (name, shape) in shapes {
+        match shape {
+            Shape::Ref(pointer) => {
+                get_ref_type(accumulator, &mut obj_table, &pointer)?;
+            }
+            shape => {
+                let path = TypePath::root(&name);
+                let ty = shape_into_json_type(accumulator, &mut obj_table, path, &shape)?;
+                accumulator.alias(&name, ty)?;
+            }
+        }
+    }
+
+    Ok(accumulator.end())
+}
+
 fn generate_object(
     accumulator: &mut dyn TypeAccumulator,
     obj_table: &mut ObjectTypeTable,
-    val: &Object,
+    object_name: &str,
+    object_path: &str,
+    fields: &[FieldShape],
 ) -> Result<(), Error> {
-    let res = val.iter().try_for_each(|(name, val)| match val {
-        JsonValue::Null => accumulator.unknown(name),
-        JsonValue::Short(_) | JsonValue::String(_) => accumulator.string(name),
-        JsonValue::Number(n) => {
-            let number = value_into_number(n)?;
-            accumulator.number(name, number)
-        }
-        JsonValue::Boolean(_) => accumulator.boolean(name),
-        JsonValue::Array(a) => {
-            let ty = get_array_element_type(accumulator, obj_table, name, a)?;
-            accumulator.array(name, ty)
+    fields.iter().try_for_each(|field| {
+        let name = &field.key;
+        let path = TypePath::field(object_name, object_path, name);
+        if field.optional {
+            let ty = shape_into_json_type(accumulator, obj_table, path, &field.shape)?;
+            return accumulator.optional(name, ty);
         }
-        JsonValue::Object(o) => {
-            let object_name = get_object_type(accumulator, obj_table, name, o)?;
-            accumulator.object(name, &object_name)
+        match &field.shape {
+            Shape::Empty | Shape::Null => accumulator.unknown(name),
+            Shape::String => accumulator.string(name),
+            Shape::DateTime(date_time) => accumulator.date_time(name, *date_time),
+            Shape::Number(number) => accumulator.number(name, *number),
+            Shape::Boolean => accumulator.boolean(name),
+            Shape::Array(a) => {
+                let ty = shape_into_json_type(accumulator, obj_table, path.element(), a)?;
+                accumulator.array(name, ty)
+            }
+            Shape::Object(o) => {
+                let object_name = get_object_type(accumulator, obj_table, path, o)?;
+                accumulator.object(name, &object_name)
+            }
+            Shape::Nullable(n) => {
+                let ty = shape_into_json_type(accumulator, obj_table, path, n)?;
+                accumulator.nullable(name, ty)
+            }
+            Shape::Union(u) => {
+                let tys = u
+                    .iter()
+                    .map(|shape| shape_into_json_type(accumulator, obj_table, path, shape))
+                    .collect::<Result<_, _>>()?;
+                accumulator.union(name, tys)
+            }
+            Shape::Ref(pointer) => {
+                let object_name = get_ref_type(accumulator, obj_table, pointer)?;
+                accumulator.object(name, &object_name)
+            }
         }
-    });
-    res
+    })
 }
 
-fn get_array_element_type(
+/// The name is reserved before the fields are generated so that nested types named after it cannot take it.
+/// Names from [`GenerateOptions::names`] win over similar objects that were already generated.
+fn get_object_type(
     accumulator: &mut dyn TypeAccumulator,
     obj_table: &mut ObjectTypeTable,
-    key: &str,
-    val: &Array,
-) -> Result<JsonType, Error> {
-    let Some(overall_val) =  val.get(0) else {
-        return Ok(JsonType::Null);
+    path: TypePath,
+    fields: &[FieldShape],
+) -> Result<String, Error> {
+    let json_path = path.json_path();
+    let overridden = obj_table.overrides.get(&json_path).cloned();
+    let mut candidates = match &overridden {
+        Some(name) => vec![name.clone()],
+        None => path.candidate_names(),
     };
-    let overall_type = value_into_json_type(accumulator, obj_table, key, overall_val)?;
-    val.iter().try_for_each(|val| {
-        if overall_type == value_into_json_type(accumulator, obj_table, key, val)? {
-            Ok(())
-        } else {
-            Err(Error::DifferingArrayType)
-        }
-    })?;
-    Ok(overall_type)
-}
+    if candidates.is_empty() {
+        candidates.push(accumulator.prefered_object_name() + &obj_table.count().to_string());
+    }
+    let name = obj_table.reserve_name(&candidates);
 
-fn value_into_number(n: &JNumber) -> Result<Number, Error> {
-    let n = *n;
-    if i64::try_from(n).is_ok() {
-        Ok(Number::Int)
-    } else if f64::try_from(n).is_ok() {
-        Ok(Number::Float)
-    } else {
-        Err(Error::BadNumber)
+    let object_fields = object_into_fields(accumulator, obj_table, &name, &json_path, fields)?;
+    let existing = match overridden {
+        Some(overridden) => obj_table
+            .has_object_name(object_fields.clone(), &overridden)
+            .then_some(overridden),
+        None => obj_table.get_object_name(object_fields.clone()),
+    };
+    match existing {
+        Some(existing) => {
+            obj_table.release_name(&name);
+            Ok(existing)
+        }
+        None => {
+            accumulator.push_object_type(&name)?;
+            generate_object(accumulator, obj_table, &name, &json_path, fields)?;
+            accumulator.pop_object_type()?;
+            obj_table.insert(&name, object_fields);
+            Ok(name)
+        }
     }
 }
 
-fn get_object_type(
+/// Named after its definition rather than where it was found, and never merged into a similar object.
+/// The name is taken before the fields are generated so that the object can refer to itself.
+fn get_ref_type(
     accumulator: &mut dyn TypeAccumulator,
     obj_table: &mut ObjectTypeTable,
-    key: &str,
-    val: &Object,
+    pointer: &str,
 ) -> Result<String, Error> {
-    let object_fields = object_into_fields(accumulator, obj_table, key, val)?;
-    if let Some(name) = obj_table.get_object_name(object_fields.clone()) {
-        Ok(name)
-    } else {
-        let name = accumulator.prefered_object_name() + &obj_table.count().to_string();
-        accumulator.push_object_type(&name)?;
-        generate_object(accumulator, obj_table, val)?;
-        accumulator.pop_object_type()?;
-        obj_table.insert(&name, object_fields);
-        Ok(name)
+    if let Some(name) = obj_table.refs.get(pointer) {
+        return Ok(name.clone());
     }
+    let (name, fields) = get_definition(obj_table, pointer)?;
+    let candidate = match name.is_empty() {
+        true => accumulator.prefered_object_name() + &obj_table.count().to_string(),
+        false => name,
+    };
+    let name = obj_table.reserve_name(&[candidate]);
+    obj_table.refs.insert(String::from(pointer), name.clone());
+
+    let object_fields = object_into_fields(accumulator, obj_table, &name, pointer, &fields)?;
+    accumulator.push_object_type(&name)?;
+    generate_object(accumulator, obj_table, &name, pointer, &fields)?;
+    accumulator.pop_object_type()?;
+    obj_table.insert(&name, object_fields);
+    Ok(name)
+}
+
+fn get_definition(
+    obj_table: &ObjectTypeTable,
+    pointer: &str,
+) -> Result<(String, Vec<FieldShape>), Error> {
+    obj_table
+        .definitions
+        .get(pointer)
+        .cloned()
+        .ok_or_else(|| Error::BadReference(String::from(pointer)))
 }
 
 /// Note that this calls [`generate_object`] if the type is an object that has not yet been generated.
-fn value_into_json_type(
+fn shape_into_json_type(
     accumulator: &mut dyn TypeAccumulator,
     obj_table: &mut ObjectTypeTable,
-    key: &str,
-    val: &JsonValue,
+    path: TypePath,
+    shape: &Shape,
 ) -> Result<JsonType, Error> {
-    Ok(match val {
-        JsonValue::Null => JsonType::Null,
-        JsonValue::Short(_) | JsonValue::String(_) => JsonType::String,
-        JsonValue::Number(n) => JsonType::Number(value_into_number(n)?),
-        JsonValue::Boolean(_) => JsonType::Boolean,
-        JsonValue::Object(o) => JsonType::Object(get_object_type(accumulator, obj_table, key, o)?),
-        JsonValue::Array(a) => JsonType::Array(Box::new(get_array_element_type(
+    Ok(match shape {
+        Shape::Empty | Shape::Null => JsonType::Null,
+        Shape::String => JsonType::String,
+        Shape::DateTime(date_time) => JsonType::DateTime(*date_time),
+        Shape::Number(n) => JsonType::Number(*n),
+        Shape::Boolean => JsonType::Boolean,
+        Shape::Object(o) => JsonType::Object(get_object_type(accumulator, obj_table, path, o)?),
+        Shape::Array(a) => JsonType::Array(Box::new(shape_into_json_type(
             accumulator,
             obj_table,
-            key,
+            path.element(),
             a,
         )?)),
+        Shape::Nullable(n) => JsonType::Nullable(Box::new(shape_into_json_type(
+            accumulator,
+            obj_table,
+            path,
+            n,
+        )?)),
+        Shape::Union(u) => JsonType::Union(
+            u.iter()
+                .map(|shape| shape_into_json_type(accumulator, obj_table, path, shape))
+                .collect::<Result<_, _>>()?,
+        ),
+        Shape::Ref(pointer) => JsonType::Object(get_ref_type(accumulator, obj_table, pointer)?),
     })
 }
 
 fn object_into_fields(
     accumulator: &mut dyn TypeAccumulator,
     obj_table: &mut ObjectTypeTable,
-    key_name: &str,
-    obj: &Object,
-) -> Result<Vec<(ObjectField, JsonType)>, Error> {
-    obj.iter()
-        .map(|(key, val)| {
+    object_name: &str,
+    object_path: &str,
+    fields: &[FieldShape],
+) -> Result<Vec<ObjectFieldType>, Error> {
+    fields
+        .iter()
+        .map(|field| {
+            let path = TypePath::field(object_name, object_path, &field.key);
             Ok((
-                key.to_owned(),
-                value_into_json_type(accumulator, obj_table, key_name, val)?,
+                field.key.to_owned(),
+                shape_into_json_type(accumulator, obj_table, path, &field.shape)?,
+                field.optional,
             ))
         })
         .collect::<Result<_, _>>()
 }
+
+fn value_into_shape(val: &JsonValue, options: &GenerateOptions) -> Result<Shape, Error> {
+    Ok(match val {
+        JsonValue::Null => Shape::Null,
+        JsonValue::Short(_) | JsonValue::String(_) => Shape::String,
+        JsonValue::Number(n) => Shape::Number(value_into_number(n)),
+        JsonValue::Boolean(_) => Shape::Boolean,
+        JsonValue::Object(o) => Shape::Object(
+            o.iter()
+                .map(|(key, val)| {
+                    Ok(FieldShape {
+                        key: key.to_owned(),
+                        shape: value_into_shape(val, options)?,
+                        optional: false,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        JsonValue::Array(a) => Shape::Array(Box::new(get_array_element_shape(a, options)?)),
+    })
+}
+
+/// Elements are merged with [`Shape::merge`], so objects with differing keys become one type.
+fn get_array_element_shape(val: &Array, options: &GenerateOptions) -> Result<Shape, Error> {
+    val.iter().try_fold(Shape::Empty, |shape, val| {
+        shape
+            .merge(value_into_shape(val, options)?, options)
+            .ok_or(Error::DifferingArrayType)
+    })
+}
+
+/// Every [`json`] number fits within a [`f64`], so anything that is not an [`i64`] is a float.
+fn value_into_number(n: &JNumber) -> Number {
+    if i64::try_from(*n).is_ok() {
+        Number::Int
+    } else {
+        Number::Float
+    }
+}
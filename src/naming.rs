@@ -0,0 +1,154 @@
+//! Helpers for naming things after JSON keys.
+
+use std::collections::HashSet;
+
+/// Split a key into words on anything that is not an ascii letter or digit.
+fn words(key: &str) -> impl Iterator<Item = &str> {
+    key.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+}
+
+/// Like [`words`], but also split on changes in case, so `userID` becomes `user` and `ID`.
+fn case_words(key: &str) -> Vec<&str> {
+    let mut acc = vec![];
+    for word in words(key) {
+        let bytes = word.as_bytes();
+        let mut start = 0;
+        for i in 1..bytes.len() {
+            let starts_word = bytes[i].is_ascii_uppercase()
+                && (!bytes[i - 1].is_ascii_uppercase()
+                    || bytes.get(i + 1).is_some_and(u8::is_ascii_lowercase));
+            if starts_word {
+                acc.push(&word[start..i]);
+                start = i;
+            }
+        }
+        acc.push(&word[start..]);
+    }
+    acc
+}
+
+/// `firstName`, `first-name` and `FirstName` all become `first_name`.
+pub fn snake_case(key: &str) -> String {
+    case_words(key)
+        .iter()
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// `first_name`, `first-name` and `FirstName` all become `firstName`.
+pub fn camel_case(key: &str) -> String {
+    case_words(key)
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let word = word.to_ascii_lowercase();
+            match i {
+                0 => word,
+                _ => word[..1].to_ascii_uppercase() + &word[1..],
+            }
+        })
+        .collect()
+}
+
+/// `first_name`, `first-name` and `firstName` all become `FirstName`.
+pub fn pascal_case(key: &str) -> String {
+    words(key)
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// A best effort attempt at turning an english plural into its singular.
+/// `users` becomes `user` and `categories` becomes `category`.
+/// Returns [`None`] if the key does not look plural.
+pub fn singular(key: &str) -> Option<String> {
+    let lower = key.to_ascii_lowercase();
+    if lower.len() > 3 && lower.ends_with("ies") {
+        Some(key[..key.len() - 3].to_owned() + "y")
+    } else if ["sses", "shes", "ches", "xes", "zes"]
+        .iter()
+        .any(|suffix| lower.ends_with(suffix))
+    {
+        Some(key[..key.len() - 2].to_owned())
+    } else if ["ss", "us", "is"]
+        .iter()
+        .any(|suffix| lower.ends_with(suffix))
+    {
+        None
+    } else if lower.len() > 1 && lower.ends_with('s') {
+        Some(key[..key.len() - 1].to_owned())
+    } else {
+        None
+    }
+}
+
+/// Whether `key` is made of ascii letters, digits and underscores without starting with a digit.
+pub fn is_identifier(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Turn `key` into an identifier as described by [`is_identifier`].
+/// `first-name` becomes `first_name`, `@id` becomes `id` and `2fa` becomes `_2fa`.
+/// Keys with nothing to build a name from, such as `""` or `_`, become `field`.
+/// Keywords are left for each language to escape.
+pub fn identifier(key: &str) -> String {
+    if is_identifier(key) && key != "_" {
+        return key.to_owned();
+    }
+    let ident = words(key).collect::<Vec<_>>().join("_");
+    if ident.is_empty() {
+        String::from("field")
+    } else if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        String::from("_") + &ident
+    } else {
+        ident
+    }
+}
+
+/// The first of `name`, `name_2`, `name_3` and so on that is not in `used` once escaped.
+/// It is added to `used`, so that keys such as `first-name` and `first_name` get separate fields.
+pub fn unique(name: &str, used: &mut HashSet<String>, escape: impl Fn(String) -> String) -> String {
+    let name = std::iter::once(name.to_owned())
+        .chain((2..).map(|n| format!("{}_{}", name, n)))
+        .map(escape)
+        .find(|name| !used.contains(name))
+        .unwrap();
+    used.insert(name.clone());
+    name
+}
+
+/// Escape `s` for a string literal, where `special` holds the quote and any other characters
+/// that the language treats specially.
+pub fn escape(s: &str, special: &[char]) -> String {
+    s.chars().fold(String::new(), |mut acc, c| {
+        match c {
+            '\\' => acc += "\\\\",
+            '\n' => acc += "\\n",
+            '\r' => acc += "\\r",
+            '\t' => acc += "\\t",
+            c if special.contains(&c) => {
+                acc.push('\\');
+                acc.push(c);
+            }
+            c => acc.push(c),
+        }
+        acc
+    })
+}
+
+/// The JSONPath segment for `key`, such as `.address` or `['first-name']`.
+pub fn json_path_segment(key: &str) -> String {
+    if is_identifier(key) {
+        format!(".{}", key)
+    } else {
+        format!("['{}']", escape(key, &['\'']))
+    }
+}
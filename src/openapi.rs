@@ -0,0 +1,84 @@
+//! Reads the schemas of OpenAPI 3 documents, see [`generate_from_openapi`].
+
+use super::*;
+use schema::{escape_pointer_segment, resolve_pointer, type_name, Definitions, SchemaReader};
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Every schema under `components/schemas`, then the request and response bodies of every
+/// operation, each with the name of the type it should become.
+pub(crate) fn openapi_into_shapes(
+    document: &JsonValue,
+    options: &GenerateOptions,
+) -> Result<(Vec<(String, Shape)>, Definitions), Error> {
+    let mut reader = SchemaReader::new(document, options);
+    let mut shapes = vec![];
+
+    for (name, _) in document["components"]["schemas"].entries() {
+        let pointer = format!("#/components/schemas/{}", escape_pointer_segment(name));
+        shapes.push((type_name(name), reader.read_ref(&pointer)?));
+    }
+
+    for (path, item) in document["paths"].entries() {
+        for method in METHODS {
+            let operation = &item[*method];
+            if !operation.is_object() {
+                continue;
+            }
+            //  Such as `listPets` to `ListPets`, or `GetPetsPetId` for `get /pets/{petId}`.
+            let operation_name = match operation["operationId"].as_str() {
+                Some(id) => naming::pascal_case(id),
+                None => naming::pascal_case(&format!("{} {}", method, path)),
+            };
+            let pointer = format!("#/paths/{}/{}", escape_pointer_segment(path), method);
+
+            if operation["requestBody"].is_object() {
+                let body = format!("{}/requestBody", pointer);
+                let name = operation_name.clone() + "Request";
+                shapes.extend(read_body(&mut reader, document, body, name)?);
+            }
+            for (status, _) in operation["responses"].entries() {
+                let body = format!("{}/responses/{}", pointer, escape_pointer_segment(status));
+                let name = format!("{}{}Response", operation_name, naming::pascal_case(status));
+                shapes.extend(read_body(&mut reader, document, body, name)?);
+            }
+        }
+    }
+
+    Ok((shapes, reader.definitions))
+}
+
+/// Bodies can be a `$ref` to `components/requestBodies` or `components/responses`.
+/// Only the first JSON media type is read, and bodies without one are skipped.
+fn read_body(
+    reader: &mut SchemaReader,
+    document: &JsonValue,
+    mut pointer: String,
+    name: String,
+) -> Result<Option<(String, Shape)>, Error> {
+    let mut body = resolve_pointer(document, &pointer).unwrap();
+    if let Some(target) = body["$ref"].as_str() {
+        body = resolve_pointer(document, target)
+            .ok_or_else(|| Error::BadReference(String::from(target)))?;
+        pointer = String::from(target);
+    }
+
+    let Some((media_type, content)) = body["content"]
+        .entries()
+        .find(|(media_type, _)| media_type.contains("json"))
+    else {
+        return Ok(None);
+    };
+    if content["schema"].is_null() {
+        return Ok(None);
+    }
+    let pointer = format!(
+        "{}/content/{}/schema",
+        pointer,
+        escape_pointer_segment(media_type)
+    );
+    let shape = reader.read_definition(&pointer, name.clone())?;
+    Ok(Some((name, shape)))
+}
@@ -0,0 +1,227 @@
+//! Reads JSON Schema documents into [`Shape`]s, see [`generate_from_schema`].
+
+use super::*;
+
+/// Object schemas reached through `$ref`, by their JSON pointer.
+/// Each has the type name it asks for, which is empty if the pointer has nothing to build one from.
+pub(crate) type Definitions = HashMap<String, (String, Vec<FieldShape>)>;
+
+/// Read the whole document as the root schema, along with every object schema it refers to.
+pub(crate) fn schema_into_shape(
+    document: &JsonValue,
+    options: &GenerateOptions,
+) -> Result<(Shape, Definitions), Error> {
+    let mut reader = SchemaReader::new(document, options);
+    let shape = reader.read_ref("#")?;
+    Ok((shape, reader.definitions))
+}
+
+pub(crate) struct SchemaReader<'a> {
+    document: &'a JsonValue,
+    options: &'a GenerateOptions,
+    pub definitions: Definitions,
+    /// Pointers that are being read, so that schemas containing themselves end.
+    resolving: HashSet<String>,
+}
+
+impl<'a> SchemaReader<'a> {
+    pub fn new(document: &'a JsonValue, options: &'a GenerateOptions) -> Self {
+        SchemaReader {
+            document,
+            options,
+            definitions: Definitions::new(),
+            resolving: HashSet::new(),
+        }
+    }
+
+    fn read(&mut self, schema: &JsonValue) -> Result<Shape, Error> {
+        //  `true`, `false` and anything else that is not a schema object.
+        if !schema.is_object() {
+            return Ok(Shape::Empty);
+        }
+
+        let shape = if let Some(pointer) = schema["$ref"].as_str() {
+            self.read_ref(pointer)?
+        } else if schema.has_key("const") {
+            value_into_shape(&schema["const"], self.options)?
+        } else if schema["enum"].is_array() {
+            let shapes = schema["enum"]
+                .members()
+                .map(|val| value_into_shape(val, self.options))
+                .collect::<Result<_, _>>()?;
+            self.merge_variants(shapes)
+        } else if schema["oneOf"].is_array() || schema["anyOf"].is_array() {
+            let variants = match schema["oneOf"].is_array() {
+                true => &schema["oneOf"],
+                false => &schema["anyOf"],
+            };
+            let shapes = variants
+                .members()
+                .map(|variant| self.read(variant))
+                .collect::<Result<_, _>>()?;
+            self.merge_variants(shapes)
+        } else if let Some(ty) = schema["type"].as_str() {
+            self.read_type(ty, schema)?
+        } else if schema["type"].is_array() {
+            let shapes = schema["type"]
+                .members()
+                .filter_map(JsonValue::as_str)
+                .map(|ty| self.read_type(ty, schema))
+                .collect::<Result<_, _>>()?;
+            self.merge_variants(shapes)
+        } else if schema.has_key("properties") {
+            self.read_type("object", schema)?
+        } else if schema.has_key("items") || schema.has_key("prefixItems") {
+            self.read_type("array", schema)?
+        } else {
+            Shape::Empty
+        };
+
+        //  From OpenAPI 3.0, which has no `null` type.
+        Ok(match schema["nullable"].as_bool() {
+            Some(true) => shape.into_nullable(),
+            _ => shape,
+        })
+    }
+
+    fn read_type(&mut self, ty: &str, schema: &JsonValue) -> Result<Shape, Error> {
+        Ok(match ty {
+            "null" => Shape::Null,
+            "boolean" => Shape::Boolean,
+            "integer" => Shape::Number(Number::Int),
+            "number" => Shape::Number(Number::Float),
+            "string" => Shape::String,
+            "array" => {
+                //  Tuples are `prefixItems` in 2020-12 and an array of `items` in draft-07.
+                let mut shapes = schema["prefixItems"]
+                    .members()
+                    .map(|item| self.read(item))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match &schema["items"] {
+                    JsonValue::Array(items) => {
+                        for item in items {
+                            shapes.push(self.read(item)?);
+                        }
+                    }
+                    JsonValue::Object(_) => shapes.push(self.read(&schema["items"])?),
+                    _ => {}
+                }
+                Shape::Array(Box::new(self.merge_variants(shapes)))
+            }
+            //  Objects without `properties` are maps, which are left unknown.
+            "object" if schema["properties"].is_object() => {
+                let required = schema["required"]
+                    .members()
+                    .filter_map(JsonValue::as_str)
+                    .collect::<Vec<_>>();
+                let fields = schema["properties"]
+                    .entries()
+                    .map(|(key, property)| {
+                        Ok(FieldShape {
+                            key: key.to_owned(),
+                            shape: self.read(property)?,
+                            optional: !required.contains(&key),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Shape::Object(fields)
+            }
+            _ => Shape::Empty,
+        })
+    }
+
+    /// Named after the last segment of the pointer, as in `#/$defs/Address`.
+    pub fn read_ref(&mut self, pointer: &str) -> Result<Shape, Error> {
+        let name = pointer
+            .rsplit('/')
+            .next()
+            .map(unescape_pointer_segment)
+            .unwrap_or_default();
+        self.read_definition(pointer, type_name(&name))
+    }
+
+    /// Object schemas become a [`Shape::Ref`] so that they keep `name` and can contain themselves.
+    /// Anything else is read in place.
+    pub fn read_definition(&mut self, pointer: &str, name: String) -> Result<Shape, Error> {
+        if self.definitions.contains_key(pointer) {
+            return Ok(Shape::Ref(String::from(pointer)));
+        }
+        if self.resolving.contains(pointer) {
+            return Ok(Shape::Empty);
+        }
+        let schema = resolve_pointer(self.document, pointer)
+            .ok_or_else(|| Error::BadReference(String::from(pointer)))?;
+
+        self.resolving.insert(String::from(pointer));
+        let is_object = schema["properties"].is_object();
+        if is_object {
+            self.definitions
+                .insert(String::from(pointer), (name, vec![]));
+        }
+        let shape = self.read(schema)?;
+        self.resolving.remove(pointer);
+
+        if !is_object {
+            return Ok(shape);
+        }
+        let (fields, nullable) = match shape {
+            Shape::Object(fields) => (fields, false),
+            Shape::Nullable(shape) if matches!(*shape, Shape::Object(_)) => match *shape {
+                Shape::Object(fields) => (fields, true),
+                _ => unreachable!(),
+            },
+            shape => {
+                self.definitions.remove(pointer);
+                return Ok(shape);
+            }
+        };
+        self.definitions.get_mut(pointer).unwrap().1 = fields;
+        let shape = Shape::Ref(String::from(pointer));
+        Ok(match nullable {
+            true => shape.into_nullable(),
+            false => shape,
+        })
+    }
+
+    /// Variants that cannot be merged are left unknown, unless [`GenerateOptions::unions`] is set.
+    fn merge_variants(&self, shapes: Vec<Shape>) -> Shape {
+        shapes
+            .into_iter()
+            .try_fold(Shape::Empty, |shape, other| {
+                shape.merge(other, self.options)
+            })
+            .unwrap_or(Shape::Empty)
+    }
+}
+
+/// Names that are already type names are kept as they are, so `UserDTO` stays but `user` becomes `User`.
+pub(crate) fn type_name(name: &str) -> String {
+    match naming::is_identifier(name) && name.starts_with(char::is_uppercase) {
+        true => name.to_owned(),
+        false => naming::pascal_case(name),
+    }
+}
+
+/// Only pointers within the document are understood, such as `#/$defs/Address`.
+pub(crate) fn resolve_pointer<'a>(document: &'a JsonValue, pointer: &str) -> Option<&'a JsonValue> {
+    let path = pointer.strip_prefix('#')?;
+    if path.is_empty() {
+        return Some(document);
+    }
+    path.strip_prefix('/')?
+        .split('/')
+        .map(unescape_pointer_segment)
+        .try_fold(document, |val, segment| match val {
+            JsonValue::Object(_) if val.has_key(&segment) => Some(&val[segment.as_str()]),
+            JsonValue::Array(vals) => vals.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+fn unescape_pointer_segment(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+pub(crate) fn escape_pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
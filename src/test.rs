@@ -27,10 +27,10 @@ num:2:Float
 str:3
 arr:4:Null
 arr:5:String
-ty:_0
+ty:MyType6
 num:a:Int
 popty
-obj:6:_0
+obj:6:MyType6
 popty
 "#
     );
@@ -75,22 +75,22 @@ fn test_mock_nested() {
         )
         .unwrap(),
         r#"ty:MyType
-ty:_0
+ty:MyType01
 num:a:Float
 popty
-ty:_1
-obj:1:_0
+ty:MyType0
+obj:1:MyType01
 popty
-obj:0:_1
-ty:_2
+obj:0:MyType0
+ty:ArrayItem
 num:a:Int
 num:b:Int
 popty
-ty:_3
+ty:OuterArrayItem
 num:num:Int
-arr:array:Object("_2")
+arr:array:Object("ArrayItem")
 popty
-arr:outer_array:Object("_3")
+arr:outer_array:Object("OuterArrayItem")
 popty
 "#
     );
@@ -99,7 +99,7 @@ popty
 #[test]
 fn test_mock_error() {
     let case = r#"
-    { "a": [{ "a": "10" }, { "a": "10", "b": 5 }] }
+    { "a": [{ "a": "10" }, { "a": 10 }] }
 "#;
     assert_eq!(
         generate(
@@ -109,14 +109,1894 @@ fn test_mock_error() {
         ),
         Err(Error::DifferingArrayType)
     );
+}
+
+#[test]
+fn test_mock_merged() {
+    let samples = [
+        r#"{ "id": 1, "user": { "name": "a" } }"#,
+        r#"{ "user": { "age": 20 }, "tags": [] }"#,
+        r#"{ "tags": ["b"] }"#,
+    ];
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            &samples
+        )
+        .unwrap(),
+        r#"ty:MyType
+opt:id:Number(Int)
+ty:User
+opt:name:String
+opt:age:Number(Int)
+popty
+opt:user:Object("User")
+opt:tags:Array(String)
+popty
+"#
+    );
+
+    let samples = [r#"{ "id": 1 }"#, r#"{ "id": "1" }"#];
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            &samples
+        ),
+        Err(Error::DifferingSampleType)
+    );
+
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            &[]
+        ),
+        Err(Error::NoSamples)
+    );
+}
+
+#[test]
+fn test_mock_optional() {
+    let json = r#"
+    {
+        "a": [
+            { "id": 1, "inner": { "b": true } },
+            { "id": 2, "name": "Bob" },
+            { "id": 3, "inner": { "b": false }, "name": "Joe" }
+        ]
+    }
+"#;
+    assert_eq!(
+        generate(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            json
+        )
+        .unwrap(),
+        r#"ty:MyType
+ty:Inner
+bool:b
+popty
+ty:AItem
+num:id:Int
+opt:inner:Object("Inner")
+opt:name:String
+popty
+arr:a:Object("AItem")
+popty
+"#
+    );
+}
 
-    let case = r#"0"#;
+#[test]
+fn test_mock_nullable() {
+    let json = r#"
+    {
+        "a": null,
+        "b": [1, null],
+        "c": [{ "x": null }, { "x": "Hello" }],
+        "d": [null, null]
+    }
+"#;
     assert_eq!(
         generate(
             accumulator_choose_with_str("mock").unwrap().as_mut(),
             "MyType",
-            case
+            json
+        )
+        .unwrap(),
+        r#"ty:MyType
+null:a
+arr:b:Nullable(Number(Int))
+ty:CItem
+nullable:x:String
+popty
+arr:c:Object("CItem")
+arr:d:Null
+popty
+"#
+    );
+}
+
+#[test]
+fn test_mock_union() {
+    let json = r#"
+    {
+        "a": [1, "two", { "x": 3 }, { "y": 4 }, ["s"], [5]],
+        "b": [{ "v": 1 }, { "v": "s" }, { "v": null }]
+    }
+"#;
+    let options = GenerateOptions {
+        unions: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        generate_with_options(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            &[json],
+            &options
+        )
+        .unwrap(),
+        r#"ty:MyType
+ty:AItem
+opt:x:Number(Int)
+opt:y:Number(Int)
+popty
+arr:a:Union([Number(Int), String, Object("AItem"), Array(Union([String, Number(Int)]))])
+ty:BItem
+nullable:v:Union([Number(Int), String])
+popty
+arr:b:Object("BItem")
+popty
+"#
+    );
+
+    assert_eq!(
+        generate(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            json
+        ),
+        Err(Error::DifferingArrayType)
+    );
+}
+
+#[test]
+fn test_mock_widening() {
+    let json = r#"
+    {
+        "a": [1, 2.5],
+        "b": [{ "x": 1 }, { "x": 1.5 }],
+        "c": [[1], [2, 3.5]]
+    }
+"#;
+    assert_eq!(
+        generate(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            json
+        )
+        .unwrap(),
+        r#"ty:MyType
+arr:a:Number(Float)
+ty:BItem
+num:x:Float
+popty
+arr:b:Object("BItem")
+arr:c:Array(Number(Float))
+popty
+"#
+    );
+
+    let samples = [r#"{ "x": 1 }"#, r#"{ "x": 0.5 }"#];
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            &samples
+        )
+        .unwrap(),
+        r#"ty:MyType
+num:x:Float
+popty
+"#
+    );
+}
+
+#[test]
+fn test_mock_root() {
+    let json = r#"[{ "a": 1 }, { "a": 2, "b": true }]"#;
+    assert_eq!(
+        generate(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            json
+        )
+        .unwrap(),
+        r#"ty:MyTypeItem
+num:a:Int
+opt:b:Boolean
+popty
+alias:MyType:Array(Object("MyTypeItem"))
+"#
+    );
+
+    let json = r#"0"#;
+    assert_eq!(
+        generate(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            json
+        )
+        .unwrap(),
+        r#"alias:MyType:Number(Int)
+"#
+    );
+}
+
+#[test]
+fn test_mock_naming() {
+    let json = r#"
+    {
+        "address": { "street": "a" },
+        "billing": { "address": { "zip": 1 } },
+        "shipping": { "street": "b" },
+        "users": [{ "name": "a" }],
+        "categories": [{ "id": 1 }],
+        "node": { "node": { "x": 1 } }
+    }
+"#;
+    assert_eq!(
+        generate(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            json
+        )
+        .unwrap(),
+        r#"ty:MyType
+ty:Address
+str:street
+popty
+obj:address:Address
+ty:BillingAddress
+num:zip:Int
+popty
+ty:Billing
+obj:address:BillingAddress
+popty
+obj:billing:Billing
+obj:shipping:Address
+ty:User
+str:name
+popty
+arr:users:Object("User")
+ty:Category
+num:id:Int
+popty
+arr:categories:Object("Category")
+ty:NodeNode
+num:x:Int
+popty
+ty:Node
+obj:node:NodeNode
+popty
+obj:node:Node
+popty
+"#
+    );
+}
+
+#[test]
+fn test_mock_name_overrides() {
+    let json = r#"
+    {
+        "data": { "items": [{ "id": 1 }], "first-name": { "id": 2 } },
+        "other": { "id": 3 }
+    }
+"#;
+    let options = GenerateOptions {
+        names: [
+            ("$.data.items[*]", "Product"),
+            ("$.data['first-name']", "Name"),
+            ("$.other", "Other"),
+        ]
+        .into_iter()
+        .map(|(path, name)| (String::from(path), String::from(name)))
+        .collect(),
+        ..Default::default()
+    };
+    assert_eq!(
+        generate_with_options(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            &[json],
+            &options
+        )
+        .unwrap(),
+        r#"ty:MyType
+ty:Product
+num:id:Int
+popty
+ty:Name
+num:id:Int
+popty
+ty:Data
+arr:items:Object("Product")
+obj:first-name:Name
+popty
+obj:data:Data
+ty:Other
+num:id:Int
+popty
+obj:other:Other
+popty
+"#
+    );
+}
+
+#[test]
+fn test_mock_schema() {
+    let schema = r##"
+    {
+        "type": "object",
+        "properties": {
+            "id": { "type": "integer" },
+            "name": { "type": ["string", "null"] },
+            "tags": { "type": "array", "items": { "enum": ["a", "b"] } },
+            "home": { "$ref": "#/$defs/address" },
+            "work": { "oneOf": [{ "$ref": "#/$defs/address" }, { "type": "null" }] },
+            "parent": { "$ref": "#" }
+        },
+        "required": ["id", "name", "tags", "home", "work"],
+        "$defs": {
+            "address": {
+                "type": "object",
+                "properties": { "street": { "type": "string" } },
+                "required": ["street"]
+            }
+        }
+    }
+"##;
+    assert_eq!(
+        generate_from_schema(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            schema
+        )
+        .unwrap(),
+        r#"ty:MyType
+num:id:Int
+nullable:name:String
+arr:tags:String
+ty:Address
+str:street
+popty
+obj:home:Address
+nullable:work:Object("Address")
+opt:parent:Object("MyType")
+popty
+"#
+    );
+
+    let schema = r##"{ "properties": { "a": { "$ref": "#/$defs/missing" } } }"##;
+    assert_eq!(
+        generate_from_schema(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            schema
+        ),
+        Err(Error::BadReference(String::from("#/$defs/missing")))
+    );
+
+    for schema in [
+        "{}",
+        r#"{ "type": "object", "additionalProperties": { "type": "string" } }"#,
+    ] {
+        assert_eq!(
+            generate_from_schema(
+                accumulator_choose_with_str("mock").unwrap().as_mut(),
+                "MyType",
+                schema
+            ),
+            Ok(String::from("alias:MyType:Null\n"))
+        );
+    }
+}
+
+#[test]
+fn test_mock_openapi() {
+    let openapi = r##"
+    {
+        "openapi": "3.0.3",
+        "paths": {
+            "/pets": {
+                "get": {
+                    "operationId": "listPets",
+                    "responses": {
+                        "200": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+                                }
+                            }
+                        }
+                    }
+                },
+                "post": {
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": { "type": "object", "properties": { "tag": { "type": "string", "nullable": true } } }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Pet": {
+                    "type": "object",
+                    "properties": { "owner": { "$ref": "#/components/schemas/user_profile" } },
+                    "required": ["owner"]
+                },
+                "user_profile": { "type": "object", "properties": { "email": { "type": "string" } } }
+            }
+        }
+    }
+"##;
+    assert_eq!(
+        generate_from_openapi(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            openapi
+        )
+        .unwrap(),
+        r#"ty:UserProfile
+opt:email:String
+popty
+ty:Pet
+obj:owner:UserProfile
+popty
+alias:ListPets200Response:Array(Object("Pet"))
+ty:PostPetsRequest
+opt:tag:Nullable(String)
+popty
+"#
+    );
+}
+
+#[test]
+fn test_mock_yaml() {
+    let yaml = r#"
+defaults: &defaults
+  retries: 3
+service:
+  <<: *defaults
+  name: api
+1: one
+---
+defaults: { retries: 1 }
+service: { name: web, retries: 2 }
+1: uno
+extra: 2.5
+"#;
+    assert_eq!(
+        generate_from_yaml(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            yaml
+        )
+        .unwrap(),
+        r#"ty:MyType
+ty:Defaults
+num:retries:Int
+popty
+obj:defaults:Defaults
+ty:Service
+num:retries:Int
+str:name
+popty
+obj:service:Service
+str:1
+opt:extra:Number(Float)
+popty
+"#
+    );
+
+    let mut accumulator = accumulator_choose_with_str("mock").unwrap();
+    assert_eq!(
+        generate_from_yaml(accumulator.as_mut(), "MyType", "a: &a [*a]"),
+        Err(Error::YamlBadAlias)
+    );
+    assert_eq!(
+        generate_from_yaml(accumulator.as_mut(), "MyType", "? [1]\n: a"),
+        Err(Error::YamlKeyNotSupported)
+    );
+    assert_eq!(
+        generate_from_yaml(accumulator.as_mut(), "MyType", "a:\n  <<: 1"),
+        Err(Error::YamlBadMerge)
+    );
+    assert_eq!(
+        generate_from_yaml(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            "c: 1.0\nd: 1e400\na: .nan\nb: .inf"
         ),
-        Err(Error::ExpectedObject)
+        Ok(String::from(
+            "ty:MyType\nnum:c:Float\nnum:d:Float\nnum:a:Float\nnum:b:Float\npopty\n"
+        ))
     );
+    assert_eq!(
+        generate_from_yaml(accumulator.as_mut(), "MyType", ""),
+        Err(Error::NoSamples)
+    );
+}
+
+#[test]
+fn test_mock_toml() {
+    let toml = r#"
+title = "example"
+released = 1979-05-27T07:32:00Z
+built = 1979-05-27T07:32:00
+day = 1979-05-27
+alarm = 07:32:00
+
+[owner]
+name = "Tom"
+
+[[servers]]
+ip = "10.0.0.1"
+port = 8000
+
+[[servers]]
+ip = "10.0.0.2"
+port = 8001.5
+"#;
+    assert_eq!(
+        generate_from_toml(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            toml
+        )
+        .unwrap(),
+        r#"ty:MyType
+str:title
+date:released:Offset
+date:built:Local
+date:day:Date
+date:alarm:Time
+ty:Owner
+str:name
+popty
+obj:owner:Owner
+ty:Server
+str:ip
+num:port:Float
+popty
+arr:servers:Object("Server")
+popty
+"#
+    );
+
+    let mut accumulator = accumulator_choose_with_str("mock").unwrap();
+    assert_eq!(
+        generate_from_toml_with_options(
+            accumulator.as_mut(),
+            "MyType",
+            &["at = 1979-05-27", "at = \"today\""],
+            &GenerateOptions::default()
+        ),
+        Ok(String::from("ty:MyType\nstr:at\npopty\n"))
+    );
+    assert_eq!(
+        generate_from_toml_with_options(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            &["at = 1979-05-27", "at = 07:32:00"],
+            &GenerateOptions::default()
+        ),
+        Ok(String::from("ty:MyType\nstr:at\npopty\n"))
+    );
+    assert!(matches!(
+        generate_from_toml(accumulator.as_mut(), "MyType", "a = [1, \"a\"]"),
+        Err(Error::DifferingArrayType)
+    ));
+    assert!(matches!(
+        generate_from_toml(accumulator.as_mut(), "MyType", "a ="),
+        Err(Error::TomlParse(_))
+    ));
+}
+
+#[test]
+fn test_mock_ndjson() {
+    let ndjson =
+        "{\"id\": 1, \"event\": \"login\"}\r\n\n{\"id\": 2.5, \"user\": {\"name\": \"a\"}}\n";
+    assert_eq!(
+        generate_from_ndjson(
+            accumulator_choose_with_str("mock").unwrap().as_mut(),
+            "MyType",
+            ndjson
+        )
+        .unwrap(),
+        r#"ty:MyType
+num:id:Float
+opt:event:String
+ty:User
+str:name
+popty
+opt:user:Object("User")
+popty
+"#
+    );
+
+    let mut accumulator = accumulator_choose_with_str("mock").unwrap();
+    assert_eq!(
+        generate_from_ndjson_with_options(
+            accumulator.as_mut(),
+            "MyType",
+            ["{\"a\": 1}".as_bytes(), "{\"a\": 2}\n".as_bytes()],
+            &GenerateOptions::default()
+        ),
+        Ok(String::from("ty:MyType\nnum:a:Int\npopty\n"))
+    );
+    assert_eq!(
+        generate_from_ndjson(accumulator.as_mut(), "MyType", "{\"a\": 1}\n\"a\""),
+        Err(Error::DifferingSampleType)
+    );
+    assert!(matches!(
+        generate_from_ndjson(accumulator.as_mut(), "MyType", "{\"a\": 1}\n{"),
+        Err(Error::Parse(_))
+    ));
+    assert_eq!(
+        generate_from_ndjson(accumulator.as_mut(), "MyType", "\n\n"),
+        Err(Error::NoSamples)
+    );
+    assert_eq!(
+        generate_from_ndjson_with_options(
+            accumulator.as_mut(),
+            "MyType",
+            [&[b'"', 0xff, b'"'][..]],
+            &GenerateOptions::default()
+        ),
+        Err(Error::Io(std::io::ErrorKind::InvalidData))
+    );
+}
+
+#[test]
+fn test_rust_identifiers() {
+    let json = r#"{ "type": 1, "first-name": "a", "2fa": true, "self": false }"#;
+    assert_eq!(
+        generate(
+            accumulator_choose_with_str("rust").unwrap().as_mut(),
+            "MyType",
+            json
+        )
+        .unwrap(),
+        r#"#[derive(Serialize, Deserialize)]
+pub struct MyType {
+	r#type: i64,
+	#[serde(rename = "first-name")]
+	first_name: String,
+	#[serde(rename = "2fa")]
+	_2fa: bool,
+	#[serde(rename = "self")]
+	self_: bool,
+}
+
+"#
+    );
+}
+
+#[test]
+fn test_rust_casing() {
+    let json = r#"{ "firstName": "a", "id": 1, "inner": { "userID": 1, "Type": "b" } }"#;
+    let mut accumulator = accumulator_choose_with_str("rust").unwrap();
+    accumulator.use_idiomatic_casing();
+    assert_eq!(
+        generate(accumulator.as_mut(), "MyType", json).unwrap(),
+        r#"#[derive(Serialize, Deserialize)]
+pub struct Inner {
+	#[serde(rename = "userID")]
+	user_id: i64,
+	#[serde(rename = "Type")]
+	r#type: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyType {
+	first_name: String,
+	id: i64,
+	inner: Inner,
+}
+
+"#
+    );
+}
+
+#[test]
+fn test_rust_duplicate_fields() {
+    let json = r#"{ "first-name": "a", "firstName": "b", "first_name": "c", "self": 1, "Self": 2, "": 3 }"#;
+    let mut accumulator = accumulator_choose_with_str("rust").unwrap();
+    accumulator.use_idiomatic_casing();
+    assert_eq!(
+        generate(accumulator.as_mut(), "MyType", json).unwrap(),
+        r#"#[derive(Serialize, Deserialize)]
+pub struct MyType {
+	#[serde(rename = "first-name")]
+	first_name: String,
+	#[serde(rename = "firstName")]
+	first_name_2: String,
+	#[serde(rename = "first_name")]
+	first_name_3: String,
+	#[serde(rename = "self")]
+	self_: i64,
+	#[serde(rename = "Self")]
+	self_2: i64,
+	#[serde(rename = "")]
+	field: i64,
+}
+
+"#
+    );
+}
+
+#[test]
+fn test_swift_duplicate_fields() {
+    let json = r#"{ "first-name": "a", "first_name": "b", "self": 1, "": 2 }"#;
+    assert_eq!(
+        generate(
+            accumulator_choose_with_str("swift").unwrap().as_mut(),
+            "MyType",
+            json
+        )
+        .unwrap(),
+        r#"class MyType: Codable {
+	var first_name: String
+	var first_name_2: String
+	var `self`: Int
+	var field: Int
+
+	enum CodingKeys: String, CodingKey {
+		case first_name = "first-name"
+		case first_name_2 = "first_name"
+		case `self`
+		case field = ""
+	}
+}
+
+//  let encoder = JSONEncoder()
+//  let json = encoder.encode(T)
+//
+//  let decoder = JSONDecoder()
+//  let ty = try decoder.decode(T.self, from: json)"#
+    );
+}
+
+#[test]
+fn test_rust_reserved_names() {
+    let json = r#"{ "option": { "a": 1 }, "string": { "b": "c" }, "address": { "d": true } }"#;
+    assert_eq!(
+        generate(
+            accumulator_choose_with_str("rust").unwrap().as_mut(),
+            "MyType",
+            json
+        )
+        .unwrap(),
+        r#"#[derive(Serialize, Deserialize)]
+pub struct MyTypeOption {
+	a: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MyTypeString {
+	b: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Address {
+	d: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MyType {
+	option: MyTypeOption,
+	string: MyTypeString,
+	address: Address,
+}
+
+"#
+    );
+}
+
+/// Covers optional, nullable, nested, array and renamed fields, as well as keys that clean up to
+/// the same field name.
+const BACKEND_SAMPLES: &[&str] = &[
+    r#"{ "id": 1, "first-name": "a", "first_name": "b", "tags": ["x"], "owner": { "name": "c" }, "note": null, "extra": true }"#,
+    r#"{ "id": 2, "first-name": "d", "first_name": "e", "tags": [], "owner": { "name": "f" }, "note": "g" }"#,
+];
+
+#[test]
+fn test_go_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("go").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r#"type Owner struct {
+	Name string `json:"name"`
+}
+
+type MyType struct {
+	Id int64 `json:"id"`
+	FirstName string `json:"first-name"`
+	FirstName_2 string `json:"first_name"`
+	Tags []string `json:"tags"`
+	Owner Owner `json:"owner"`
+	Note *string `json:"note"`
+	Extra *bool `json:"extra,omitempty"`
+}
+
+//  import "encoding/json"
+//
+//  var ty T
+//  err := json.Unmarshal(data, &ty)
+//  data, err := json.Marshal(ty)"#
+    );
+}
+
+#[test]
+fn test_go_field_names() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("go").unwrap().as_mut(),
+            "MyType",
+            &[
+                r#"{"a_b": 1, "aB": 2, "2fa": true, "n": [null, 1], "m": null}"#,
+                r#"{"a_b": 1, "aB": 2, "2fa": true, "n": [], "m": 1.5}"#
+            ]
+        )
+        .unwrap(),
+        r#"type MyType struct {
+	AB int64 `json:"a_b"`
+	AB_2 int64 `json:"aB"`
+	X2fa bool `json:"2fa"`
+	N []*int64 `json:"n"`
+	M *float64 `json:"m"`
+}
+
+//  import "encoding/json"
+//
+//  var ty T
+//  err := json.Unmarshal(data, &ty)
+//  data, err := json.Marshal(ty)"#
+    );
+}
+
+#[test]
+fn test_csharp_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("csharp").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r#"public record Owner
+{
+	[JsonPropertyName("name")]
+	public required string Name { get; init; }
+}
+
+public record MyType
+{
+	[JsonPropertyName("id")]
+	public required long Id { get; init; }
+	[JsonPropertyName("first-name")]
+	public required string FirstName { get; init; }
+	[JsonPropertyName("first_name")]
+	public required string FirstName_2 { get; init; }
+	[JsonPropertyName("tags")]
+	public required List<string> Tags { get; init; }
+	[JsonPropertyName("owner")]
+	public required Owner Owner { get; init; }
+	[JsonPropertyName("note")]
+	public required string? Note { get; init; }
+	[JsonPropertyName("extra")]
+	public bool? Extra { get; init; }
+}
+
+//  #nullable enable
+//  using System.Collections.Generic;
+//  using System.Text.Json;
+//  using System.Text.Json.Serialization;
+//
+//  var json = JsonSerializer.Serialize(ty);
+//  var ty = JsonSerializer.Deserialize<T>(json);"#
+    );
+}
+
+#[test]
+fn test_csharp_field_names() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("csharp").unwrap().as_mut(),
+            "MyType",
+            &[r#"{"myType": 1, "2fa": true, "n": [null, 1], "value": "v"}"#]
+        )
+        .unwrap(),
+        r#"public record MyType
+{
+	[JsonPropertyName("myType")]
+	public required long MyTypeValue { get; init; }
+	[JsonPropertyName("2fa")]
+	public required bool _2fa { get; init; }
+	[JsonPropertyName("n")]
+	public required List<long?> N { get; init; }
+	[JsonPropertyName("value")]
+	public required string Value { get; init; }
+}
+
+//  #nullable enable
+//  using System.Collections.Generic;
+//  using System.Text.Json;
+//  using System.Text.Json.Serialization;
+//
+//  var json = JsonSerializer.Serialize(ty);
+//  var ty = JsonSerializer.Deserialize<T>(json);"#
+    );
+}
+
+#[test]
+fn test_cpp_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("cpp").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r#"struct Owner {
+	std::string name;
+};
+
+NLOHMANN_DEFINE_TYPE_NON_INTRUSIVE(Owner, name)
+
+struct MyType {
+	int64_t id;
+	std::string first_name;
+	std::string first_name_2;
+	std::vector<std::string> tags;
+	Owner owner;
+	std::optional<std::string> note;
+	std::optional<bool> extra;
+};
+
+inline void to_json(nlohmann::json& j, const MyType& v) {
+	j = nlohmann::json::object();
+	j["id"] = v.id;
+	j["first-name"] = v.first_name;
+	j["first_name"] = v.first_name_2;
+	j["tags"] = v.tags;
+	j["owner"] = v.owner;
+	j["note"] = v.note ? nlohmann::json(*v.note) : nlohmann::json(nullptr);
+	if (v.extra) {
+		j["extra"] = *v.extra;
+	}
+}
+
+inline void from_json(const nlohmann::json& j, MyType& v) {
+	j.at("id").get_to(v.id);
+	j.at("first-name").get_to(v.first_name);
+	j.at("first_name").get_to(v.first_name_2);
+	j.at("tags").get_to(v.tags);
+	j.at("owner").get_to(v.owner);
+	if (!j.at("note").is_null()) {
+		v.note = j.at("note").get<std::string>();
+	}
+	if (j.contains("extra") && !j.at("extra").is_null()) {
+		v.extra = j.at("extra").get<bool>();
+	}
+}
+
+//  #include <cstdint>
+//  #include <optional>
+//  #include <string>
+//  #include <vector>
+//  #include <nlohmann/json.hpp>
+//
+//  T ty = nlohmann::json::parse(str).get<T>();
+//  std::string str = nlohmann::json(ty).dump();"#
+    );
+}
+
+#[test]
+fn test_cpp_type_named_members() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("cpp").unwrap().as_mut(),
+            "MyType",
+            &[r#"{"List": {"a": 1}, "maybe": [null, "s"], "x": {"List": 1}}"#]
+        )
+        .unwrap(),
+        r#"namespace nlohmann {
+template <typename T>
+struct adl_serializer<std::optional<T>> {
+	static void to_json(json& j, const std::optional<T>& v) {
+		j = v ? json(*v) : json(nullptr);
+	}
+
+	static void from_json(const json& j, std::optional<T>& v) {
+		v = j.is_null() ? std::nullopt : std::optional<T>(j.get<T>());
+	}
+};
+}
+
+struct List {
+	int64_t a;
+};
+
+NLOHMANN_DEFINE_TYPE_NON_INTRUSIVE(List, a)
+
+struct X {
+	int64_t List_;
+};
+
+inline void to_json(nlohmann::json& j, const X& v) {
+	j = nlohmann::json::object();
+	j["List"] = v.List_;
+}
+
+inline void from_json(const nlohmann::json& j, X& v) {
+	j.at("List").get_to(v.List_);
+}
+
+struct MyType {
+	List List_;
+	std::vector<std::optional<std::string>> maybe;
+	X x;
+};
+
+inline void to_json(nlohmann::json& j, const MyType& v) {
+	j = nlohmann::json::object();
+	j["List"] = v.List_;
+	j["maybe"] = v.maybe;
+	j["x"] = v.x;
+}
+
+inline void from_json(const nlohmann::json& j, MyType& v) {
+	j.at("List").get_to(v.List_);
+	j.at("maybe").get_to(v.maybe);
+	j.at("x").get_to(v.x);
+}
+
+//  #include <cstdint>
+//  #include <optional>
+//  #include <string>
+//  #include <vector>
+//  #include <nlohmann/json.hpp>
+//
+//  T ty = nlohmann::json::parse(str).get<T>();
+//  std::string str = nlohmann::json(ty).dump();"#
+    );
+}
+
+#[test]
+fn test_zod_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("zod").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r#"import { z } from "zod";
+
+export const OwnerSchema = z.object({
+	name: z.string(),
+});
+export type Owner = z.infer<typeof OwnerSchema>;
+
+export const MyTypeSchema = z.object({
+	id: z.number().int(),
+	"first-name": z.string(),
+	first_name: z.string(),
+	tags: z.array(z.string()),
+	owner: OwnerSchema,
+	note: z.string().nullable(),
+	extra: z.boolean().optional(),
+});
+export type MyType = z.infer<typeof MyTypeSchema>;
+
+"#
+    );
+}
+
+#[test]
+fn test_zod_field_names() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("zod").unwrap().as_mut(),
+            "MyType",
+            &[
+                r#"{"a-b": 1, "n": null, "ids": [1, 2.5]}"#,
+                r#"{"a-b": 2, "n": "x", "ids": [], "extra": [null, true]}"#
+            ]
+        )
+        .unwrap(),
+        r#"import { z } from "zod";
+
+export const MyTypeSchema = z.object({
+	"a-b": z.number().int(),
+	n: z.string().nullable(),
+	ids: z.array(z.number()),
+	extra: z.array(z.boolean().nullable()).optional(),
+});
+export type MyType = z.infer<typeof MyTypeSchema>;
+
+"#
+    );
+}
+
+#[test]
+fn test_jsonschema_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("jsonschema").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "MyType",
+  "type": "object",
+  "properties": {
+    "id": {
+      "type": "integer"
+    },
+    "first-name": {
+      "type": "string"
+    },
+    "first_name": {
+      "type": "string"
+    },
+    "tags": {
+      "type": "array",
+      "items": {
+        "type": "string"
+      }
+    },
+    "owner": {
+      "$ref": "#/$defs/Owner"
+    },
+    "note": {
+      "type": [
+        "string",
+        "null"
+      ]
+    },
+    "extra": {
+      "type": "boolean"
+    }
+  },
+  "required": [
+    "id",
+    "first-name",
+    "first_name",
+    "tags",
+    "owner",
+    "note"
+  ],
+  "$defs": {
+    "Owner": {
+      "type": "object",
+      "properties": {
+        "name": {
+          "type": "string"
+        }
+      },
+      "required": [
+        "name"
+      ]
+    }
+  }
+}"##
+    );
+}
+
+#[test]
+fn test_jsonschema_refs() {
+    let schema = r##"{
+        "$ref": "#/$defs/Tree",
+        "$defs": {
+            "Tree": {
+                "type": "object",
+                "properties": { "children": { "type": "array", "items": { "$ref": "#/$defs/Tree" } } }
+            }
+        }
+    }"##;
+    let res = generate_from_schema(
+        accumulator_choose_with_str("jsonschema").unwrap().as_mut(),
+        "Tree",
+        schema,
+    )
+    .unwrap();
+    assert_eq!(
+        json::parse(&res).unwrap(),
+        json::object! {
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Tree",
+            "type": "object",
+            "properties": { "children": { "type": "array", "items": { "$ref": "#" } } },
+        }
+    );
+
+    let openapi = r#"{
+        "openapi": "3.0.0",
+        "components": {
+            "schemas": {
+                "Pet": { "type": "object", "properties": { "tags": { "type": "array", "items": {} } } },
+                "Name": { "type": "string" }
+            }
+        }
+    }"#;
+    let res = generate_from_openapi(
+        accumulator_choose_with_str("jsonschema").unwrap().as_mut(),
+        openapi,
+    )
+    .unwrap();
+    assert_eq!(
+        json::parse(&res).unwrap(),
+        json::object! {
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$defs": {
+                "Pet": {
+                    "type": "object",
+                    "properties": { "tags": { "type": "array", "items": {} } },
+                },
+                "Name": { "type": "string" },
+            },
+        }
+    );
+}
+
+#[test]
+fn test_pydantic_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("pydantic").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r#"from typing import Optional
+from pydantic import BaseModel, Field
+
+
+class Owner(BaseModel):
+	name: str
+
+
+class MyType(BaseModel):
+	id: int
+	first_name: str = Field(alias="first-name")
+	first_name_2: str = Field(alias="first_name")
+	tags: list[str]
+	owner: Owner
+	note: Optional[str] = None
+	extra: Optional[bool] = None
+
+
+"#
+    );
+}
+
+#[test]
+fn test_pydantic_model_attributes() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("pydantic").unwrap().as_mut(),
+            "MyType",
+            &[r#"{"model_config": 1, "json": "a", "copy": true, "validate": 1, "schema": 2, "fields": [1], "ok": 1}"#]
+        )
+        .unwrap(),
+        r#"from pydantic import BaseModel, Field
+
+
+class MyType(BaseModel):
+	field_model_config: int = Field(alias="model_config")
+	json_: str = Field(alias="json")
+	copy_: bool = Field(alias="copy")
+	validate_: int = Field(alias="validate")
+	schema_: int = Field(alias="schema")
+	fields_: list[int] = Field(alias="fields")
+	ok: int
+
+
+"#
+    );
+}
+
+#[test]
+fn test_dataclass_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("dataclass").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r#"from dataclasses import dataclass
+from typing import Any, Optional
+
+
+@dataclass
+class Owner:
+	name: str
+
+	@classmethod
+	def from_dict(cls, d: dict[str, Any]) -> "Owner":
+		return cls(
+			name=d["name"],
+		)
+
+	def to_dict(self) -> dict[str, Any]:
+		d: dict[str, Any] = {
+			"name": self.name,
+		}
+		return d
+
+
+@dataclass
+class MyType:
+	id: int
+	first_name: str
+	first_name_2: str
+	tags: list[str]
+	owner: Owner
+	note: Optional[str]
+	extra: Optional[bool] = None
+
+	@classmethod
+	def from_dict(cls, d: dict[str, Any]) -> "MyType":
+		return cls(
+			id=d["id"],
+			first_name=d["first-name"],
+			first_name_2=d["first_name"],
+			tags=d["tags"],
+			owner=Owner.from_dict(d["owner"]),
+			note=d["note"],
+			extra=d.get("extra"),
+		)
+
+	def to_dict(self) -> dict[str, Any]:
+		d: dict[str, Any] = {
+			"id": self.id,
+			"first-name": self.first_name,
+			"first_name": self.first_name_2,
+			"tags": self.tags,
+			"owner": self.owner.to_dict(),
+			"note": self.note,
+		}
+		if self.extra is not None:
+			d["extra"] = self.extra
+		return d
+
+
+"#
+    );
+}
+
+#[test]
+fn test_dataclass_field_names() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("dataclass").unwrap().as_mut(),
+            "MyType",
+            &[
+                r#"{"from_dict": 1, "grid": [[{"a": 1}]], "maybe": null, "class": "c"}"#,
+                r#"{"from_dict": 2, "grid": [], "maybe": {"a": 2}, "class": "d"}"#
+            ]
+        )
+        .unwrap(),
+        r#"from dataclasses import dataclass
+from typing import Any, Optional
+
+
+@dataclass
+class GridItem:
+	a: int
+
+	@classmethod
+	def from_dict(cls, d: dict[str, Any]) -> "GridItem":
+		return cls(
+			a=d["a"],
+		)
+
+	def to_dict(self) -> dict[str, Any]:
+		d: dict[str, Any] = {
+			"a": self.a,
+		}
+		return d
+
+
+@dataclass
+class MyType:
+	from_dict_2: int
+	grid: list[list[GridItem]]
+	maybe: Optional[GridItem]
+	class_: str
+
+	@classmethod
+	def from_dict(cls, d: dict[str, Any]) -> "MyType":
+		return cls(
+			from_dict_2=d["from_dict"],
+			grid=[[GridItem.from_dict(i1) for i1 in i0] for i0 in d["grid"]],
+			maybe=None if d["maybe"] is None else GridItem.from_dict(d["maybe"]),
+			class_=d["class"],
+		)
+
+	def to_dict(self) -> dict[str, Any]:
+		d: dict[str, Any] = {
+			"from_dict": self.from_dict_2,
+			"grid": [[i1.to_dict() for i1 in i0] for i0 in self.grid],
+			"maybe": None if self.maybe is None else self.maybe.to_dict(),
+			"class": self.class_,
+		}
+		return d
+
+
+"#
+    );
+}
+
+#[test]
+fn test_elm_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("elm").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r#"import Json.Decode as Decode exposing (Decoder)
+import Json.Encode as Encode
+
+
+andMap : Decoder a -> Decoder (a -> b) -> Decoder b
+andMap =
+    Decode.map2 (|>)
+
+
+optionalField : String -> Decoder a -> Decoder (Maybe a)
+optionalField key decoder =
+    Decode.maybe (Decode.field key Decode.value)
+        |> Decode.andThen
+            (\field ->
+                case field of
+                    Nothing ->
+                        Decode.succeed Nothing
+
+                    Just _ ->
+                        Decode.field key (Decode.nullable decoder)
+            )
+
+
+encodeMaybe : (a -> Encode.Value) -> Maybe a -> Encode.Value
+encodeMaybe encoder =
+    Maybe.map encoder >> Maybe.withDefault Encode.null
+
+
+type alias Owner =
+    { name : String
+    }
+
+
+decoderOwner : Decoder Owner
+decoderOwner =
+    Decode.succeed Owner
+        |> andMap (Decode.field "name" Decode.string)
+
+
+encodeOwner : Owner -> Encode.Value
+encodeOwner value =
+    Encode.object
+        [ ( "name", Encode.string value.name )
+        ]
+
+
+type alias MyType =
+    { id : Int
+    , firstName : String
+    , firstName_2 : String
+    , tags : List String
+    , owner : Owner
+    , note : Maybe String
+    , extra : Maybe Bool
+    }
+
+
+decoderMyType : Decoder MyType
+decoderMyType =
+    Decode.succeed MyType
+        |> andMap (Decode.field "id" Decode.int)
+        |> andMap (Decode.field "first-name" Decode.string)
+        |> andMap (Decode.field "first_name" Decode.string)
+        |> andMap (Decode.field "tags" (Decode.list Decode.string))
+        |> andMap (Decode.field "owner" decoderOwner)
+        |> andMap (Decode.field "note" (Decode.nullable Decode.string))
+        |> andMap (optionalField "extra" Decode.bool)
+
+
+encodeMyType : MyType -> Encode.Value
+encodeMyType value =
+    Encode.object
+        [ ( "id", Encode.int value.id )
+        , ( "first-name", Encode.string value.firstName )
+        , ( "first_name", Encode.string value.firstName_2 )
+        , ( "tags", Encode.list Encode.string value.tags )
+        , ( "owner", encodeOwner value.owner )
+        , ( "note", encodeMaybe Encode.string value.note )
+        , ( "extra", encodeMaybe Encode.bool value.extra )
+        ]
+
+
+"#
+    );
+}
+
+#[test]
+fn test_elm_field_names() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("elm").unwrap().as_mut(),
+            "MyType",
+            &[r#"{"type": 1, "2fa": true, "grid": [[1, null]]}"#]
+        )
+        .unwrap(),
+        r#"import Json.Decode as Decode exposing (Decoder)
+import Json.Encode as Encode
+
+
+andMap : Decoder a -> Decoder (a -> b) -> Decoder b
+andMap =
+    Decode.map2 (|>)
+
+
+encodeMaybe : (a -> Encode.Value) -> Maybe a -> Encode.Value
+encodeMaybe encoder =
+    Maybe.map encoder >> Maybe.withDefault Encode.null
+
+
+type alias MyType =
+    { type_ : Int
+    , field2fa : Bool
+    , grid : List (List (Maybe Int))
+    }
+
+
+decoderMyType : Decoder MyType
+decoderMyType =
+    Decode.succeed MyType
+        |> andMap (Decode.field "type" Decode.int)
+        |> andMap (Decode.field "2fa" Decode.bool)
+        |> andMap (Decode.field "grid" (Decode.list (Decode.list (Decode.nullable Decode.int))))
+
+
+encodeMyType : MyType -> Encode.Value
+encodeMyType value =
+    Encode.object
+        [ ( "type", Encode.int value.type_ )
+        , ( "2fa", Encode.bool value.field2fa )
+        , ( "grid", Encode.list (Encode.list (encodeMaybe Encode.int)) value.grid )
+        ]
+
+
+"#
+    );
+}
+
+#[test]
+fn test_haskell_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("haskell").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r#"{-# LANGUAGE DeriveGeneric #-}
+
+import Data.Aeson
+import Data.Maybe (fromMaybe)
+import Data.Text (Text)
+import GHC.Generics (Generic)
+
+-- | Maps record fields back to their JSON keys.
+jsonOptions :: [(String, String)] -> Options
+jsonOptions keys =
+  defaultOptions {fieldLabelModifier = \field -> fromMaybe field (lookup field keys)}
+
+data Owner = Owner
+  { ownerName :: Text
+  }
+  deriving (Show, Eq, Generic)
+
+instance FromJSON Owner where
+  parseJSON = genericParseJSON optionsOwner
+
+instance ToJSON Owner where
+  toJSON = genericToJSON optionsOwner
+  toEncoding = genericToEncoding optionsOwner
+
+optionsOwner :: Options
+optionsOwner =
+  jsonOptions
+    [ ("ownerName", "name")
+    ]
+
+data MyType = MyType
+  { myTypeId :: Int
+  , myTypeFirstName :: Text
+  , myTypeFirstName_2 :: Text
+  , myTypeTags :: [Text]
+  , myTypeOwner :: Owner
+  , myTypeNote :: Maybe Text
+  , myTypeExtra :: Maybe Bool
+  }
+  deriving (Show, Eq, Generic)
+
+instance FromJSON MyType where
+  parseJSON = genericParseJSON optionsMyType
+
+instance ToJSON MyType where
+  toJSON = genericToJSON optionsMyType
+  toEncoding = genericToEncoding optionsMyType
+
+optionsMyType :: Options
+optionsMyType =
+  jsonOptions
+    [ ("myTypeId", "id")
+    , ("myTypeFirstName", "first-name")
+    , ("myTypeFirstName_2", "first_name")
+    , ("myTypeTags", "tags")
+    , ("myTypeOwner", "owner")
+    , ("myTypeNote", "note")
+    , ("myTypeExtra", "extra")
+    ]
+
+"#
+    );
+}
+
+#[test]
+fn test_haskell_field_names() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("haskell").unwrap().as_mut(),
+            "MyType",
+            &[
+                r#"{"myTypeA": 1, "a": 2, "": "e", "n": [null, 1]}"#,
+                r#"{"myTypeA": 1, "a": 2, "": "e", "n": null}"#
+            ]
+        )
+        .unwrap(),
+        r#"{-# LANGUAGE DeriveGeneric #-}
+
+import Data.Aeson
+import Data.Maybe (fromMaybe)
+import Data.Text (Text)
+import GHC.Generics (Generic)
+
+-- | Maps record fields back to their JSON keys.
+jsonOptions :: [(String, String)] -> Options
+jsonOptions keys =
+  defaultOptions {fieldLabelModifier = \field -> fromMaybe field (lookup field keys)}
+
+data MyType = MyType
+  { myTypeMyTypeA :: Int
+  , myTypeA :: Int
+  , myTypeField :: Text
+  , myTypeN :: Maybe ([Maybe Int])
+  }
+  deriving (Show, Eq, Generic)
+
+instance FromJSON MyType where
+  parseJSON = genericParseJSON optionsMyType
+
+instance ToJSON MyType where
+  toJSON = genericToJSON optionsMyType
+  toEncoding = genericToEncoding optionsMyType
+
+optionsMyType :: Options
+optionsMyType =
+  jsonOptions
+    [ ("myTypeMyTypeA", "myTypeA")
+    , ("myTypeA", "a")
+    , ("myTypeField", "")
+    , ("myTypeN", "n")
+    ]
+
+"#
+    );
+}
+
+#[test]
+fn test_scala_output() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("scala").unwrap().as_mut(),
+            "MyType",
+            BACKEND_SAMPLES
+        )
+        .unwrap(),
+        r#"import io.circe.{Decoder, Encoder}
+import io.circe.generic.semiauto.{deriveDecoder, deriveEncoder}
+
+final case class Owner(
+	name: String,
+)
+
+object Owner {
+	implicit val decoder: Decoder[Owner] = deriveDecoder[Owner]
+	implicit val encoder: Encoder[Owner] = deriveEncoder[Owner]
+}
+
+final case class MyType(
+	id: Long,
+	`first-name`: String,
+	first_name: String,
+	tags: List[String],
+	owner: Owner,
+	note: Option[String],
+	extra: Option[Boolean],
+)
+
+object MyType {
+	implicit val decoder: Decoder[MyType] = deriveDecoder[MyType]
+	implicit val encoder: Encoder[MyType] = deriveEncoder[MyType]
+}
+
+"#
+    );
+}
+
+#[test]
+fn test_scala_field_names() {
+    assert_eq!(
+        generate_merged(
+            accumulator_choose_with_str("scala").unwrap().as_mut(),
+            "MyType",
+            &[r#"{"type": 1, "a-b": "x", "2fa": true}"#]
+        )
+        .unwrap(),
+        r#"import io.circe.{Decoder, Encoder}
+import io.circe.generic.semiauto.{deriveDecoder, deriveEncoder}
+
+final case class MyType(
+	`type`: Long,
+	`a-b`: String,
+	`2fa`: Boolean,
+)
+
+object MyType {
+	implicit val decoder: Decoder[MyType] = deriveDecoder[MyType]
+	implicit val encoder: Encoder[MyType] = deriveEncoder[MyType]
+}
+
+"#
+    );
+}
+
+#[test]
+fn test_recursive_types() {
+    let schema = r##"{
+        "$ref": "#/$defs/Node",
+        "$defs": {
+            "Node": {
+                "type": "object",
+                "properties": {
+                    "value": { "type": "integer" },
+                    "next": { "anyOf": [{ "$ref": "#/$defs/Node" }, { "type": "null" }] },
+                    "parent": { "$ref": "#/$defs/Node" },
+                    "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } }
+                },
+                "required": ["value", "next", "parent", "children"]
+            }
+        }
+    }"##;
+    let res = generate_from_schema(
+        accumulator_choose_with_str("rust").unwrap().as_mut(),
+        "Tree",
+        schema,
+    )
+    .unwrap();
+    assert_eq!(res, "#[derive(Serialize, Deserialize)]\npub struct Tree {\n\tvalue: i64,\n\tnext: Option<Box<Tree>>,\n\tparent: Box<Tree>,\n\tchildren: Vec<Tree>,\n}\n\n");
+    let res = generate_from_schema(
+        accumulator_choose_with_str("cpp").unwrap().as_mut(),
+        "Tree",
+        schema,
+    )
+    .unwrap();
+    assert_eq!(res, "struct Tree {\n\tint64_t value;\n\tstd::shared_ptr<Tree> next;\n\tstd::shared_ptr<Tree> parent;\n\tstd::vector<Tree> children;\n};\n\ninline void to_json(nlohmann::json& j, const Tree& v) {\n\tj = nlohmann::json::object();\n\tj[\"value\"] = v.value;\n\tj[\"next\"] = v.next ? nlohmann::json(*v.next) : nlohmann::json(nullptr);\n\tj[\"parent\"] = v.parent ? nlohmann::json(*v.parent) : nlohmann::json(nullptr);\n\tj[\"children\"] = v.children;\n}\n\ninline void from_json(const nlohmann::json& j, Tree& v) {\n\tj.at(\"value\").get_to(v.value);\n\tif (!j.at(\"next\").is_null()) {\n\t\tv.next = std::make_shared<Tree>(j.at(\"next\").get<Tree>());\n\t}\n\tif (!j.at(\"parent\").is_null()) {\n\t\tv.parent = std::make_shared<Tree>(j.at(\"parent\").get<Tree>());\n\t}\n\tj.at(\"children\").get_to(v.children);\n}\n\n//  #include <cstdint>\n//  #include <memory>\n//  #include <optional>\n//  #include <string>\n//  #include <vector>\n//  #include <nlohmann/json.hpp>\n//\n//  T ty = nlohmann::json::parse(str).get<T>();\n//  std::string str = nlohmann::json(ty).dump();");
+    let res = generate_from_schema(
+        accumulator_choose_with_str("zod").unwrap().as_mut(),
+        "Tree",
+        schema,
+    )
+    .unwrap();
+    assert_eq!(res, "import { z } from \"zod\";\n\nexport type Tree = {\n\tvalue: number;\n\tnext: Tree | null;\n\tparent: Tree;\n\tchildren: Array<Tree>;\n};\nexport const TreeSchema: z.ZodType<Tree> = z.object({\n\tvalue: z.number().int(),\n\tnext: z.lazy(() => TreeSchema).nullable(),\n\tparent: z.lazy(() => TreeSchema),\n\tchildren: z.array(z.lazy(() => TreeSchema)),\n});\n\n");
+    let res = generate_from_schema(
+        accumulator_choose_with_str("go").unwrap().as_mut(),
+        "Tree",
+        schema,
+    )
+    .unwrap();
+    assert_eq!(res, "type Tree struct {\n\tValue int64 `json:\"value\"`\n\tNext *Tree `json:\"next\"`\n\tParent *Tree `json:\"parent\"`\n\tChildren []Tree `json:\"children\"`\n}\n\n//  import \"encoding/json\"\n//\n//  var ty T\n//  err := json.Unmarshal(data, &ty)\n//  data, err := json.Marshal(ty)");
+    let res = generate_from_schema(
+        accumulator_choose_with_str("elm").unwrap().as_mut(),
+        "Tree",
+        schema,
+    )
+    .unwrap();
+    assert_eq!(res, "import Json.Decode as Decode exposing (Decoder)\nimport Json.Encode as Encode\n\n\nandMap : Decoder a -> Decoder (a -> b) -> Decoder b\nandMap =\n    Decode.map2 (|>)\n\n\nencodeMaybe : (a -> Encode.Value) -> Maybe a -> Encode.Value\nencodeMaybe encoder =\n    Maybe.map encoder >> Maybe.withDefault Encode.null\n\n\ntype Tree\n    = Tree\n        { value : Int\n        , next : Maybe Tree\n        , parent : Tree\n        , children : List Tree\n        }\n\n\ndecoderTree : Decoder Tree\ndecoderTree =\n    Decode.succeed (\\field1 field2 field3 field4 -> Tree { value = field1, next = field2, parent = field3, children = field4 })\n        |> andMap (Decode.field \"value\" Decode.int)\n        |> andMap (Decode.field \"next\" (Decode.nullable (Decode.lazy (\\_ -> decoderTree))))\n        |> andMap (Decode.field \"parent\" (Decode.lazy (\\_ -> decoderTree)))\n        |> andMap (Decode.field \"children\" (Decode.list (Decode.lazy (\\_ -> decoderTree))))\n\n\nencodeTree : Tree -> Encode.Value\nencodeTree (Tree value) =\n    Encode.object\n        [ ( \"value\", Encode.int value.value )\n        , ( \"next\", encodeMaybe encodeTree value.next )\n        , ( \"parent\", encodeTree value.parent )\n        , ( \"children\", Encode.list encodeTree value.children )\n        ]\n\n\n");
+    let res = generate_from_schema(
+        accumulator_choose_with_str("python").unwrap().as_mut(),
+        "Tree",
+        schema,
+    )
+    .unwrap();
+    assert_eq!(res, "class Tree(TypedDict):\n\tvalue: int\n\tnext: Optional[\"Tree\"]\n\tparent: \"Tree\"\n\tchildren: list[\"Tree\"]\n\n\n# This accumulator relies on `TypedDict`.\n# Import with `from typing import TypedDict, Optional`.\n# Note that this is only supported on Python 3.8+");
+    let res = generate_from_schema(
+        accumulator_choose_with_str("dataclass").unwrap().as_mut(),
+        "Tree",
+        schema,
+    )
+    .unwrap();
+    assert_eq!(res, "from dataclasses import dataclass\nfrom typing import Any, Optional\n\n\n@dataclass\nclass Tree:\n\tvalue: int\n\tnext: Optional[\"Tree\"]\n\tparent: \"Tree\"\n\tchildren: list[\"Tree\"]\n\n\t@classmethod\n\tdef from_dict(cls, d: dict[str, Any]) -> \"Tree\":\n\t\treturn cls(\n\t\t\tvalue=d[\"value\"],\n\t\t\tnext=None if d[\"next\"] is None else Tree.from_dict(d[\"next\"]),\n\t\t\tparent=Tree.from_dict(d[\"parent\"]),\n\t\t\tchildren=[Tree.from_dict(i0) for i0 in d[\"children\"]],\n\t\t)\n\n\tdef to_dict(self) -> dict[str, Any]:\n\t\td: dict[str, Any] = {\n\t\t\t\"value\": self.value,\n\t\t\t\"next\": None if self.next is None else self.next.to_dict(),\n\t\t\t\"parent\": self.parent.to_dict(),\n\t\t\t\"children\": [i0.to_dict() for i0 in self.children],\n\t\t}\n\t\treturn d\n\n\n");
+    let res = generate_from_schema(
+        accumulator_choose_with_str("pydantic").unwrap().as_mut(),
+        "Tree",
+        schema,
+    )
+    .unwrap();
+    assert_eq!(res, "from typing import Optional\nfrom pydantic import BaseModel\n\n\nclass Tree(BaseModel):\n\tvalue: int\n\tnext: Optional[\"Tree\"] = None\n\tparent: \"Tree\"\n\tchildren: list[\"Tree\"]\n\n\nTree.model_rebuild()\n");
+}
+
+#[test]
+fn test_toml_date_times() {
+    let toml = r#"
+starts = 1979-05-27T07:32:00Z
+local = 1979-05-27T07:32:00
+day = 1979-05-27
+alarm = 07:32:00
+maybe = [1979-05-27]
+"#;
+    let res = generate_from_toml(
+        accumulator_choose_with_str("typescript").unwrap().as_mut(),
+        "Event",
+        toml,
+    )
+    .unwrap();
+    assert_eq!(res, "type Event = {\n\tstarts: string;\n\tlocal: string;\n\tday: string;\n\talarm: string;\n\tmaybe: string[];\n}\n\n");
+    let res = generate_from_toml(
+        accumulator_choose_with_str("zod").unwrap().as_mut(),
+        "Event",
+        toml,
+    )
+    .unwrap();
+    assert_eq!(res, "import { z } from \"zod\";\n\nexport const EventSchema = z.object({\n\tstarts: z.string().datetime({ offset: true }),\n\tlocal: z.string().datetime({ local: true }),\n\tday: z.string().date(),\n\talarm: z.string().time(),\n\tmaybe: z.array(z.string().date()),\n});\nexport type Event = z.infer<typeof EventSchema>;\n\n");
+    let res = generate_from_toml(
+        accumulator_choose_with_str("dataclass").unwrap().as_mut(),
+        "Event",
+        toml,
+    )
+    .unwrap();
+    assert_eq!(res, "from dataclasses import dataclass\nfrom datetime import date, datetime, time\nfrom typing import Any\n\n\n@dataclass\nclass Event:\n\tstarts: datetime\n\tlocal: datetime\n\tday: date\n\talarm: time\n\tmaybe: list[date]\n\n\t@classmethod\n\tdef from_dict(cls, d: dict[str, Any]) -> \"Event\":\n\t\treturn cls(\n\t\t\tstarts=datetime.fromisoformat(d[\"starts\"]),\n\t\t\tlocal=datetime.fromisoformat(d[\"local\"]),\n\t\t\tday=date.fromisoformat(d[\"day\"]),\n\t\t\talarm=time.fromisoformat(d[\"alarm\"]),\n\t\t\tmaybe=[date.fromisoformat(i0) for i0 in d[\"maybe\"]],\n\t\t)\n\n\tdef to_dict(self) -> dict[str, Any]:\n\t\td: dict[str, Any] = {\n\t\t\t\"starts\": self.starts.isoformat(),\n\t\t\t\"local\": self.local.isoformat(),\n\t\t\t\"day\": self.day.isoformat(),\n\t\t\t\"alarm\": self.alarm.isoformat(),\n\t\t\t\"maybe\": [i0.isoformat() for i0 in self.maybe],\n\t\t}\n\t\treturn d\n\n\n");
 }
@@ -0,0 +1,47 @@
+//! Reads TOML documents into [`Shape`]s, see [`generate_from_toml`].
+//! Unlike YAML, TOML cannot become a [`JsonValue`] since it has dates and times.
+
+use super::*;
+use toml::Value;
+
+pub(crate) fn toml_into_shape(toml_str: &str, options: &GenerateOptions) -> Result<Shape, Error> {
+    let val = toml_str.parse::<Value>().map_err(Error::TomlParse)?;
+    toml_value_into_shape(&val, options)
+}
+
+fn toml_value_into_shape(val: &Value, options: &GenerateOptions) -> Result<Shape, Error> {
+    Ok(match val {
+        Value::String(_) => Shape::String,
+        Value::Integer(_) => Shape::Number(Number::Int),
+        Value::Float(_) => Shape::Number(Number::Float),
+        Value::Boolean(_) => Shape::Boolean,
+        Value::Datetime(datetime) => {
+            Shape::DateTime(match (&datetime.date, &datetime.time, &datetime.offset) {
+                (Some(_), Some(_), Some(_)) => DateTime::Offset,
+                (Some(_), Some(_), None) => DateTime::Local,
+                (Some(_), None, _) => DateTime::Date,
+                (None, _, _) => DateTime::Time,
+            })
+        }
+        //  Arrays of tables, as in `[[servers]]`, are merged like arrays of objects.
+        Value::Array(a) => Shape::Array(Box::new(a.iter().try_fold(
+            Shape::Empty,
+            |shape, val| {
+                shape
+                    .merge(toml_value_into_shape(val, options)?, options)
+                    .ok_or(Error::DifferingArrayType)
+            },
+        )?)),
+        Value::Table(t) => Shape::Object(
+            t.iter()
+                .map(|(key, val)| {
+                    Ok(FieldShape {
+                        key: key.to_owned(),
+                        shape: toml_value_into_shape(val, options)?,
+                        optional: false,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+    })
+}
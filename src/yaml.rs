@@ -0,0 +1,86 @@
+//! Reads YAML documents into [`Shape`]s, see [`generate_from_yaml`].
+//! YAML is not read into a [`JsonValue`], which would turn floats such as `1.0` into ints.
+
+use super::*;
+use yaml_rust::{Yaml, YamlLoader};
+
+/// Every document in the stream is its own sample.
+pub(crate) fn yaml_into_shapes(
+    yaml_str: &str,
+    options: &GenerateOptions,
+) -> Result<Vec<Shape>, Error> {
+    YamlLoader::load_from_str(yaml_str)
+        .map_err(Error::YamlParse)?
+        .iter()
+        .map(|yaml| yaml_into_shape(yaml, options))
+        .collect()
+}
+
+fn yaml_into_shape(yaml: &Yaml, options: &GenerateOptions) -> Result<Shape, Error> {
+    Ok(match yaml {
+        Yaml::Null => Shape::Null,
+        Yaml::Boolean(_) => Shape::Boolean,
+        Yaml::Integer(_) => Shape::Number(Number::Int),
+        //  Including `.inf` and `.nan`, even if JSON cannot hold them.
+        Yaml::Real(_) => Shape::Number(Number::Float),
+        Yaml::String(_) => Shape::String,
+        Yaml::Array(a) => Shape::Array(Box::new(a.iter().try_fold(
+            Shape::Empty,
+            |shape, yaml| {
+                shape
+                    .merge(yaml_into_shape(yaml, options)?, options)
+                    .ok_or(Error::DifferingArrayType)
+            },
+        )?)),
+        Yaml::Hash(h) => {
+            let mut fields: Vec<FieldShape> = vec![];
+            for (key, val) in h {
+                match (key.as_str(), val) {
+                    //  Merge keys, as in `<<: *defaults`, give the keys that are not already set.
+                    (Some("<<"), _) => {
+                        let merged = match val {
+                            Yaml::Array(a) => a.iter().collect::<Vec<_>>(),
+                            val => vec![val],
+                        };
+                        for merged in merged {
+                            let Shape::Object(merged) = yaml_into_shape(merged, options)? else {
+                                Err(Error::YamlBadMerge)?
+                            };
+                            for field in merged {
+                                if !fields.iter().any(|other| other.key == field.key) {
+                                    fields.push(field);
+                                }
+                            }
+                        }
+                    }
+                    _ => {
+                        let field = FieldShape {
+                            key: yaml_into_key(key)?,
+                            shape: yaml_into_shape(val, options)?,
+                            optional: false,
+                        };
+                        match fields.iter_mut().find(|other| other.key == field.key) {
+                            Some(other) => *other = field,
+                            None => fields.push(field),
+                        }
+                    }
+                }
+            }
+            Shape::Object(fields)
+        }
+        //  An alias to an anchor that is undefined or still being defined.
+        Yaml::Alias(_) | Yaml::BadValue => Err(Error::YamlBadAlias)?,
+    })
+}
+
+/// Scalar keys are written as they would be in JSON, so `1: a` has the key `"1"`.
+fn yaml_into_key(key: &Yaml) -> Result<String, Error> {
+    Ok(match key {
+        Yaml::String(s) | Yaml::Real(s) => s.clone(),
+        Yaml::Integer(n) => n.to_string(),
+        Yaml::Boolean(b) => b.to_string(),
+        Yaml::Null => String::from("null"),
+        Yaml::Array(_) | Yaml::Hash(_) => Err(Error::YamlKeyNotSupported)?,
+        Yaml::Alias(_) | Yaml::BadValue => Err(Error::YamlBadAlias)?,
+    })
+}